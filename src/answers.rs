@@ -0,0 +1,139 @@
+use crate::integrity::{self, IntegrityError};
+use std::{collections::HashMap, env, fmt::Display, fs, io};
+
+pub const ANSWERS_PATH: &str = "src/answers.txt";
+
+/// recorded answers, keyed by `(day, part)`. unknown answers map to `None`.
+pub type Answers = HashMap<(u8, u8), Option<String>>;
+
+pub fn parse(contents: &str) -> Answers {
+    contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| {
+            let (key, value) = line.split_once('=')?;
+            let (day, part) = key.trim().split_once('.')?;
+            let value = value.trim().replace("\\n", "\n");
+
+            Some((
+                (day.parse().ok()?, part.parse().ok()?),
+                (!value.is_empty()).then_some(value),
+            ))
+        })
+        .collect()
+}
+
+pub fn load() -> io::Result<Answers> {
+    let path = env::current_dir()?.join(ANSWERS_PATH);
+    fs::read_to_string(path).map(|contents| parse(&contents))
+}
+
+/// lines to append to the answers file for a newly added day.
+pub fn stub(day: u8) -> String {
+    format!("{day:02}.1 =\n{day:02}.2 =\n")
+}
+
+/// checks the solutions of `day` against the answers recorded for the real input.
+/// skips without failing if the input or the answers are not present.
+pub fn check<A: Display, B: Display>(
+    day: u8,
+    part_one: impl Fn(&str) -> Option<A>,
+    part_two: impl Fn(&str) -> Option<B>,
+) {
+    let input_path = env::current_dir()
+        .unwrap()
+        .join(format!("src/inputs/{day:02}.txt"));
+    let input = match integrity::read(&input_path) {
+        Ok(input) if !input.is_empty() => input,
+        Err(e @ IntegrityError::ChecksumMismatch { .. }) => {
+            panic!("input of day {day} is corrupted: {e}")
+        }
+        Err(IntegrityError::EncryptionError(e)) => {
+            eprintln!("skipping day {day}: {e}");
+            return;
+        }
+        _ => {
+            eprintln!(
+                "skipping day {day}: no input at \"{}\".",
+                input_path.display()
+            );
+            return;
+        }
+    };
+
+    let answers = match load() {
+        Ok(answers) => answers,
+        Err(e) => {
+            eprintln!("skipping day {day}: could not read \"{ANSWERS_PATH}\": {e}");
+            return;
+        }
+    };
+
+    let expected = [1, 2].map(|part| answers.get(&(day, part)).cloned().flatten());
+    if expected.iter().all(Option::is_none) {
+        eprintln!("skipping day {day}: no answers recorded in \"{ANSWERS_PATH}\".");
+        return;
+    }
+
+    let mut failures = Vec::new();
+    for (part, expected) in expected.iter().enumerate() {
+        let Some(expected) = expected else {
+            continue;
+        };
+
+        let result = match part {
+            0 => part_one(&input).map(|r| r.to_string()),
+            _ => part_two(&input).map(|r| r.to_string()),
+        };
+
+        if result.as_ref() != Some(expected) {
+            failures.push(format!(
+                "part {}\n  expected: {expected}\n       got: {}",
+                part + 1,
+                result.unwrap_or_else(|| "not solved.".into())
+            ));
+        }
+    }
+
+    assert!(
+        failures.is_empty(),
+        "day {day} does not match the answers recorded in \"{ANSWERS_PATH}\":\n{}",
+        failures.join("\n")
+    );
+}
+
+/// generates a test that checks a day's solutions against its recorded answers.
+/// only compiled with the `real-inputs` feature, e.g. `cargo test --features real-inputs`.
+#[macro_export]
+macro_rules! input_tests {
+    ($day:expr) => {
+        #[cfg(all(test, feature = "real-inputs"))]
+        mod input_tests {
+            #[test]
+            fn test_input() {
+                $crate::answers::check($day, super::part_one, super::part_two);
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let answers = parse("# comment\n01.1 = 24000\n01.2 =\n10.2 = ##..\\n..##\n");
+
+        assert_eq!(answers[&(1, 1)], Some("24000".to_string()));
+        assert_eq!(answers[&(1, 2)], None);
+        assert_eq!(answers[&(10, 2)], Some("##..\n..##".to_string()));
+        assert_eq!(answers.get(&(2, 1)), None);
+    }
+
+    #[test]
+    fn test_stub() {
+        assert_eq!(parse(&stub(7)).len(), 2);
+    }
+}
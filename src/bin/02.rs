@@ -67,13 +67,12 @@ fn determine_outcome_for_game(elf_shape: &Shape, my_shape: &Shape) -> Outcome {
         .into_iter()
         .cycle()
         .skip_while(|s| s != elf_shape)
-        .skip(1) // Elf's shape
-        .next() // Winner vs elf's shape
+        .nth(1) // Winner vs elf's shape
         .expect("cycle iteration should never end");
     if *my_shape == my_winner {
-        return Win;
+        Win
     } else {
-        return Lose;
+        Lose
     }
 }
 
@@ -84,16 +83,14 @@ fn determine_shape_for_outcome(elf_shape: &Shape, outcome: &Outcome) -> Shape {
             .into_iter()
             .cycle()
             .skip_while(|s| s != elf_shape)
-            .skip(1) // Elf's shape
-            .next() // Winner vs elf's shape
+            .nth(1) // Winner vs elf's shape
             .expect("cycle iteration should never end"),
         Lose => SHAPE_ORDER
             .into_iter()
             .rev()
             .cycle()
             .skip_while(|s| s != elf_shape)
-            .skip(1) // Elf's shape
-            .next() // Loser vs elf's shape
+            .nth(1) // Loser vs elf's shape
             .expect("cycle iteration should never end"),
     }
 }
@@ -130,19 +127,5 @@ fn main() {
     advent_of_code::solve!(2, part_two, input);
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let input = advent_of_code::read_file("examples", 2);
-        assert_eq!(part_one(&input), Some(15));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let input = advent_of_code::read_file("examples", 2);
-        assert_eq!(part_two(&input), Some(12));
-    }
-}
+advent_of_code::example_tests!(2);
+advent_of_code::input_tests!(2);
@@ -1,4 +1,4 @@
-use std::{collections::HashSet, io::BufRead};
+use std::collections::HashSet;
 
 fn item_to_priority(item: &char) -> u32 {
     let mut priority = 1 + item.to_ascii_lowercase() as u32 - 'a' as u32;
@@ -57,19 +57,5 @@ fn main() {
     advent_of_code::solve!(2, part_two, input);
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let input = advent_of_code::read_file("examples", 3);
-        assert_eq!(part_one(&input), Some(157));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let input = advent_of_code::read_file("examples", 3);
-        assert_eq!(part_two(&input), Some(70));
-    }
-}
+advent_of_code::example_tests!(3);
+advent_of_code::input_tests!(3);
@@ -70,7 +70,7 @@ pub fn part_one(input: &str) -> Option<String> {
 
     let top_crates = crates
         .into_iter()
-        .map(|stack| stack.last().unwrap().clone())
+        .map(|stack| *stack.last().unwrap())
         .collect();
 
     Some(top_crates)
@@ -86,7 +86,7 @@ pub fn part_two(input: &str) -> Option<String> {
 
     let top_crates = crates
         .into_iter()
-        .map(|stack| stack.last().unwrap().clone())
+        .map(|stack| *stack.last().unwrap())
         .collect();
 
     Some(top_crates)
@@ -98,19 +98,5 @@ fn main() {
     advent_of_code::solve!(2, part_two, input);
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let input = advent_of_code::read_file("examples", 5);
-        assert_eq!(part_one(&input), Some(String::from("CMZ")));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let input = advent_of_code::read_file("examples", 5);
-        assert_eq!(part_two(&input), Some(String::from("MCD")));
-    }
-}
+advent_of_code::example_tests!(5);
+advent_of_code::input_tests!(5);
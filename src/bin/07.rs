@@ -112,7 +112,7 @@ fn traverse_sizes(node: &Node) -> Vec<u32> {
             .borrow()
             .children
             .values()
-            .flat_map(|p| traverse_sizes(p))
+            .flat_map(traverse_sizes)
             .collect();
         sizes.append(&mut child_sizes);
     }
@@ -154,19 +154,5 @@ fn main() {
     advent_of_code::solve!(2, part_two, input);
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let input = advent_of_code::read_file("examples", 7);
-        assert_eq!(part_one(&input), Some(95437));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let input = advent_of_code::read_file("examples", 7);
-        assert_eq!(part_two(&input), Some(24933642));
-    }
-}
+advent_of_code::example_tests!(7);
+advent_of_code::input_tests!(7);
@@ -106,7 +106,7 @@ pub fn part_two(input: &str) -> Option<String> {
 
         for pending in queue.iter_mut() {
             if let Some(val) = pending.tick() {
-                rx += val as i32;
+                rx += val;
             }
         }
         queue.retain(|e| e.n_cycles > 0);
@@ -121,30 +121,5 @@ fn main() {
     advent_of_code::solve!(2, part_two, input);
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let input = advent_of_code::read_file("examples", 10);
-        assert_eq!(part_one(&input), Some(13140));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let input = advent_of_code::read_file("examples", 10);
-        assert_eq!(
-            part_two(&input),
-            Some(
-                "##..##..##..##..##..##..##..##..##..##..
-###...###...###...###...###...###...###.
-####....####....####....####....####....
-#####.....#####.....#####.....#####.....
-######......######......######......####
-#######.......#######.......#######....."
-                    .to_owned()
-            )
-        );
-    }
-}
+advent_of_code::example_tests!(10);
+advent_of_code::input_tests!(10);
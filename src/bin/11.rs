@@ -43,10 +43,10 @@ impl Monkey {
     fn parse(desc: &str) -> Monkey {
         let mut lines = desc
             .lines()
-            .map(move |l: &str| l.split(':').last().unwrap());
+            .map(move |l: &str| l.split(':').next_back().unwrap());
         lines.next();
         let items_desc = lines.next().unwrap();
-        let operation_desc = lines.next().unwrap().split('=').last().unwrap();
+        let operation_desc = lines.next().unwrap().split('=').next_back().unwrap();
         let test_desc = lines.next().unwrap();
         let true_desc = lines.next().unwrap();
         let false_desc = lines.next().unwrap();
@@ -154,19 +154,5 @@ fn main() {
     advent_of_code::solve!(2, part_two, input);
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let input = advent_of_code::read_file("examples", 11);
-        assert_eq!(part_one(&input), Some(10605));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let input = advent_of_code::read_file("examples", 11);
-        assert_eq!(part_two(&input), Some(2713310158));
-    }
-}
+advent_of_code::example_tests!(11);
+advent_of_code::input_tests!(11);
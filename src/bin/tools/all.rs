@@ -0,0 +1,62 @@
+use crate::cli::{CliError, Command, Flag, Format, Global, Matches};
+use crate::solve::{answer_lines, run_day};
+use advent_of_code::{days::DAYS, parse_exec_time, parse_results};
+use advent_of_code::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use serde_json::json;
+
+pub const COMMAND: Command = Command {
+    name: "all",
+    about: "Run the solutions of all days",
+    args: "",
+    flags: &[Flag::switch("-r", "--release", "Build in release mode")],
+    run,
+};
+
+fn run(global: &Global, matches: &Matches) -> Result<(), CliError> {
+    let release = matches.flag("--release");
+
+    let outputs: Vec<(u8, String)> = DAYS
+        .iter()
+        .map(|&day| {
+            run_day(day, release, false)
+                .map(|output| (day, output))
+                .map_err(|e| CliError::Failed(format!("Failed to run cargo: {e}")))
+        })
+        .collect::<Result<_, _>>()?;
+    let total: f64 = outputs
+        .iter()
+        .map(|(_, output)| parse_exec_time(output))
+        .sum();
+
+    match global.format {
+        Format::Json => {
+            let days: Vec<_> = outputs
+                .iter()
+                .map(|(day, output)| json!({ "day": day, "parts": parse_results(output) }))
+                .collect();
+            println!("{:#}", json!({ "days": days, "total_ms": total }));
+        }
+        Format::Text if global.quiet => {
+            for (day, output) in &outputs {
+                print!("{}", answer_lines(*day, &parse_results(output)));
+            }
+        }
+        Format::Text => {
+            for (day, output) in &outputs {
+                println!("----------");
+                println!("{ANSI_BOLD}| Day {day:02} |{ANSI_RESET}");
+                println!("----------");
+                println!(
+                    "{}",
+                    if output.is_empty() {
+                        "Not solved."
+                    } else {
+                        output.trim()
+                    }
+                );
+            }
+            println!("{ANSI_BOLD}Total:{ANSI_RESET} {ANSI_ITALIC}{total:.2}ms{ANSI_RESET}");
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,129 @@
+use crate::cli::{CliError, Command, Flag, Format, Global, Matches};
+use crate::solve::run_day;
+use advent_of_code::{days::DAYS, parse_results, ANSI_BOLD, ANSI_RESET};
+use serde::Serialize;
+use std::{collections::BTreeMap, time::Duration};
+
+const DEFAULT_RUNS: u32 = 10;
+
+pub const COMMAND: Command = Command {
+    name: "bench",
+    about: "Measure the solutions of a day, or of all days",
+    args: "[day]",
+    flags: &[Flag::value(
+        "-n",
+        "--runs",
+        "count",
+        "Number of runs per day, defaults to 10",
+    )],
+    run,
+};
+
+#[derive(Serialize)]
+struct Timing {
+    part: u8,
+    min_ms: f64,
+    median_ms: f64,
+    mean_ms: f64,
+}
+
+#[derive(Serialize)]
+struct DayTimings {
+    day: u8,
+    runs: u32,
+    parts: Vec<Timing>,
+}
+
+fn timing(part: u8, mut samples: Vec<f64>) -> Timing {
+    samples.sort_by(f64::total_cmp);
+    let mid = samples.len() / 2;
+    let median_ms = if samples.len() % 2 == 0 {
+        (samples[mid - 1] + samples[mid]) / 2_f64
+    } else {
+        samples[mid]
+    };
+
+    Timing {
+        part,
+        min_ms: samples[0],
+        median_ms,
+        mean_ms: samples.iter().sum::<f64>() / samples.len() as f64,
+    }
+}
+
+/// runs the solution of `day` in release mode `runs` times.
+fn bench_day(day: u8, runs: u32) -> Result<DayTimings, CliError> {
+    let mut samples: BTreeMap<u8, Vec<f64>> = BTreeMap::new();
+
+    for _ in 0..runs {
+        let output = run_day(day, true, true)
+            .map_err(|e| CliError::Failed(format!("Failed to run cargo: {e}")))?;
+        for result in parse_results(&output) {
+            if let Some(elapsed) = result.elapsed_ms {
+                samples.entry(result.part).or_default().push(elapsed);
+            }
+        }
+    }
+
+    Ok(DayTimings {
+        day,
+        runs,
+        parts: samples
+            .into_iter()
+            .map(|(part, samples)| timing(part, samples))
+            .collect(),
+    })
+}
+
+fn format_ms(ms: f64) -> String {
+    format!("{:.2?}", Duration::from_secs_f64(ms / 1000_f64))
+}
+
+fn run(global: &Global, matches: &Matches) -> Result<(), CliError> {
+    let days = match matches.opt_day(0)? {
+        Some(day) => vec![day],
+        None => DAYS.to_vec(),
+    };
+    let runs = matches.value("--runs")?.unwrap_or(DEFAULT_RUNS);
+    if runs == 0 {
+        return Err(CliError::InvalidValue("--runs".into(), runs.to_string()));
+    }
+
+    let mut timings = Vec::new();
+    for day in days {
+        if global.format == Format::Text && !global.quiet {
+            eprintln!("Running day {day:02} {runs} times...");
+        }
+        timings.push(bench_day(day, runs)?);
+    }
+
+    match global.format {
+        Format::Json => println!("{:#}", serde_json::json!(timings)),
+        Format::Text => {
+            for day in &timings {
+                if !global.quiet {
+                    println!("{ANSI_BOLD}Day {:02}{ANSI_RESET}", day.day);
+                }
+                for part in &day.parts {
+                    if global.quiet {
+                        println!(
+                            "{:02}.{} = {}",
+                            day.day,
+                            part.part,
+                            format_ms(part.median_ms)
+                        );
+                    } else {
+                        println!(
+                            "  Part {}  min {}  median {}  mean {}",
+                            part.part,
+                            format_ms(part.min_ms),
+                            format_ms(part.median_ms),
+                            format_ms(part.mean_ms)
+                        );
+                    }
+                }
+            }
+        }
+    }
+    Ok(())
+}
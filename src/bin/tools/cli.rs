@@ -0,0 +1,327 @@
+use std::{
+    collections::{HashMap, HashSet},
+    env,
+    ffi::OsString,
+    fmt::{self, Display, Write},
+    process::Command as Process,
+    str::FromStr,
+};
+
+/// a flag of a command, e.g. `-f, --force` or `-t, --template <path>`.
+pub struct Flag {
+    pub short: Option<&'static str>,
+    pub long: &'static str,
+    /// name of the value the flag takes, `None` for switches.
+    pub value: Option<&'static str>,
+    pub help: &'static str,
+}
+
+impl Flag {
+    pub const fn switch(short: &'static str, long: &'static str, help: &'static str) -> Self {
+        Flag {
+            short: Some(short),
+            long,
+            value: None,
+            help,
+        }
+    }
+
+    pub const fn value(
+        short: &'static str,
+        long: &'static str,
+        value: &'static str,
+        help: &'static str,
+    ) -> Self {
+        Flag {
+            short: Some(short),
+            long,
+            value: Some(value),
+            help,
+        }
+    }
+
+    fn names(&self) -> String {
+        let value = self.value.map(|v| format!(" <{v}>")).unwrap_or_default();
+        match self.short {
+            Some(short) => format!("{short}, {}{value}", self.long),
+            None => format!("    {}{value}", self.long),
+        }
+    }
+}
+
+/// a subcommand of `cargo aoc`, used to parse its arguments and to generate help.
+pub struct Command {
+    pub name: &'static str,
+    pub about: &'static str,
+    /// positional arguments, e.g. `<day> [part]`.
+    pub args: &'static str,
+    pub flags: &'static [Flag],
+    pub run: fn(&Global, &Matches) -> Result<(), CliError>,
+}
+
+const YEAR: Flag = Flag::value(
+    "-y",
+    "--year",
+    "year",
+    "Year of the event, defaults to the current one",
+);
+const FORMAT: Flag = Flag {
+    short: None,
+    long: "--format",
+    value: Some("text|json"),
+    help: "Output format of `solve`, `all` and `bench`",
+};
+const QUIET: Flag = Flag::switch("-q", "--quiet", "Only print results");
+const HELP: Flag = Flag::switch("-h", "--help", "Print help");
+
+pub const GLOBAL_FLAGS: &[Flag] = &[YEAR, FORMAT, QUIET, HELP];
+
+#[derive(Debug)]
+pub enum CliError {
+    UnknownCommand(String),
+    MissingArgument(&'static str),
+    InvalidValue(String, String),
+    UnknownFlag(String),
+    Unsupported(&'static str),
+    Parse(pico_args::Error),
+    /// the arguments were fine, but running the command failed.
+    Failed(String),
+}
+
+impl CliError {
+    /// whether the error is about the arguments, rather than running the command.
+    pub fn is_usage(&self) -> bool {
+        !matches!(self, CliError::Failed(_))
+    }
+}
+
+impl Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CliError::UnknownCommand(name) => write!(f, "unknown command \"{name}\"."),
+            CliError::MissingArgument(name) => write!(f, "missing argument <{name}>."),
+            CliError::InvalidValue(name, value) => write!(f, "invalid {name} \"{value}\"."),
+            CliError::UnknownFlag(flag) => write!(f, "unknown flag \"{flag}\"."),
+            CliError::Unsupported(what) => write!(f, "{what} is not supported by this command."),
+            CliError::Parse(e) => write!(f, "{e}"),
+            CliError::Failed(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl From<pico_args::Error> for CliError {
+    fn from(e: pico_args::Error) -> Self {
+        CliError::Parse(e)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            _ => Err(format!("unknown format \"{s}\", expected `text` or `json`")),
+        }
+    }
+}
+
+/// flags shared by all commands.
+pub struct Global {
+    pub year: Option<u16>,
+    pub format: Format,
+    pub quiet: bool,
+}
+
+impl Global {
+    /// fails for commands that only print text.
+    pub fn require_text(&self) -> Result<(), CliError> {
+        match self.format {
+            Format::Text => Ok(()),
+            Format::Json => Err(CliError::Unsupported("`--format json`")),
+        }
+    }
+}
+
+/// arguments of a command, parsed according to its `Command`.
+pub struct Matches {
+    switches: HashSet<&'static str>,
+    values: HashMap<&'static str, String>,
+    free: Vec<String>,
+}
+
+impl Matches {
+    pub fn flag(&self, long: &str) -> bool {
+        self.switches.contains(long)
+    }
+
+    pub fn value<T: FromStr>(&self, long: &'static str) -> Result<Option<T>, CliError> {
+        self.values
+            .get(long)
+            .map(|value| {
+                value
+                    .parse()
+                    .map_err(|_| CliError::InvalidValue(long.into(), value.clone()))
+            })
+            .transpose()
+    }
+
+    pub fn opt_free<T: FromStr>(
+        &self,
+        idx: usize,
+        name: &'static str,
+    ) -> Result<Option<T>, CliError> {
+        self.free
+            .get(idx)
+            .map(|value| {
+                value
+                    .parse()
+                    .map_err(|_| CliError::InvalidValue(name.into(), value.clone()))
+            })
+            .transpose()
+    }
+
+    pub fn free<T: FromStr>(&self, idx: usize, name: &'static str) -> Result<T, CliError> {
+        self.opt_free(idx, name)?
+            .ok_or(CliError::MissingArgument(name))
+    }
+
+    pub fn opt_day(&self, idx: usize) -> Result<Option<u8>, CliError> {
+        match self.opt_free::<u8>(idx, "day")? {
+            Some(day) if !(1..=25).contains(&day) => {
+                Err(CliError::InvalidValue("day".into(), day.to_string()))
+            }
+            day => Ok(day),
+        }
+    }
+
+    pub fn day(&self, idx: usize) -> Result<u8, CliError> {
+        self.opt_day(idx)?.ok_or(CliError::MissingArgument("day"))
+    }
+}
+
+pub enum Invocation {
+    Run(&'static Command, Global, Matches),
+    Help(Option<&'static Command>),
+}
+
+fn keys(flag: &Flag) -> pico_args::Keys {
+    match flag.short {
+        Some(short) => [short, flag.long].into(),
+        None => flag.long.into(),
+    }
+}
+
+pub fn parse(argv: Vec<OsString>, commands: &[&'static Command]) -> Result<Invocation, CliError> {
+    let mut args = pico_args::Arguments::from_vec(argv);
+
+    let help = args.contains(keys(&HELP));
+    let global = Global {
+        year: args.opt_value_from_str(keys(&YEAR))?,
+        format: args
+            .opt_value_from_str(keys(&FORMAT))?
+            .unwrap_or(Format::Text),
+        quiet: args.contains(keys(&QUIET)),
+    };
+
+    let name = match args.subcommand()? {
+        Some(name) if name == "help" => {
+            let topic: Option<String> = args.opt_free_from_str()?;
+            return match topic {
+                Some(topic) => find(commands, &topic).map(|c| Invocation::Help(Some(c))),
+                None => Ok(Invocation::Help(None)),
+            };
+        }
+        Some(name) => name,
+        None => return Ok(Invocation::Help(None)),
+    };
+    let command = find(commands, &name)?;
+    if help {
+        return Ok(Invocation::Help(Some(command)));
+    }
+
+    let mut matches = Matches {
+        switches: HashSet::new(),
+        values: HashMap::new(),
+        free: Vec::new(),
+    };
+    for flag in command.flags {
+        if flag.value.is_some() {
+            if let Some(value) = args.opt_value_from_str::<_, String>(keys(flag))? {
+                matches.values.insert(flag.long, value);
+            }
+        } else if args.contains(keys(flag)) {
+            matches.switches.insert(flag.long);
+        }
+    }
+
+    for arg in args.finish() {
+        let arg = arg.to_string_lossy().into_owned();
+        if arg.starts_with('-') && arg.len() > 1 {
+            return Err(CliError::UnknownFlag(arg));
+        }
+        matches.free.push(arg);
+    }
+
+    Ok(Invocation::Run(command, global, matches))
+}
+
+fn find(commands: &[&'static Command], name: &str) -> Result<&'static Command, CliError> {
+    commands
+        .iter()
+        .find(|c| c.name == name)
+        .copied()
+        .ok_or_else(|| CliError::UnknownCommand(name.to_string()))
+}
+
+pub fn usage(command: &Command) -> String {
+    format!("Usage: cargo aoc {} [flags] {}", command.name, command.args)
+}
+
+fn write_flags(help: &mut String, title: &str, flags: &[Flag]) {
+    if flags.is_empty() {
+        return;
+    }
+    writeln!(help, "\n{title}:").unwrap();
+    for flag in flags {
+        writeln!(help, "  {:<28} {}", flag.names(), flag.help).unwrap();
+    }
+}
+
+pub fn help(commands: &[&Command], command: Option<&Command>) -> String {
+    let mut help = String::new();
+    match command {
+        Some(command) => {
+            writeln!(help, "{}\n\n{}", command.about, usage(command)).unwrap();
+            write_flags(&mut help, "Flags", command.flags);
+        }
+        None => {
+            writeln!(
+                help,
+                "🎄 Advent of Code helpers\n\nUsage: cargo aoc <command> [flags]\n"
+            )
+            .unwrap();
+            writeln!(help, "Commands:").unwrap();
+            for command in commands {
+                writeln!(help, "  {:<12} {}", command.name, command.about).unwrap();
+            }
+            writeln!(help, "  {:<12} Print help for a command", "help").unwrap();
+        }
+    }
+    write_flags(&mut help, "Global flags", GLOBAL_FLAGS);
+    help
+}
+
+/// runs another command of this binary, e.g. to scaffold a day after downloading it.
+pub fn run_command(args: &[String]) -> bool {
+    env::current_exe()
+        .and_then(|exe| Process::new(exe).args(args).status())
+        .is_ok_and(|status| status.success())
+}
@@ -1,46 +1,139 @@
-/*
- * This file contains template code.
- * There is no need to edit this file unless you want to change template functionality.
- */
-use advent_of_code::aoc_cli;
-use std::process;
-
-struct Args {
-    day: u8,
-    year: Option<u16>,
+use crate::cli::{run_command, CliError, Command, Flag, Global, Matches};
+use advent_of_code::aoc_cli::{self, AocCliError};
+use advent_of_code::calendar::{current_event_year, current_year, format_duration, unlock_time};
+use advent_of_code::encryption::{self, EncryptionError};
+use std::{
+    io::{self, Write},
+    path::Path,
+    process, thread,
+    time::{Duration, SystemTime},
+};
+
+/// the site can be slow to respond in the first seconds after the unlock.
+const RETRY_ATTEMPTS: u32 = 5;
+const RETRY_DELAY: Duration = Duration::from_secs(3);
+
+pub const COMMAND: Command = Command {
+    name: "download",
+    about: "Download the input and puzzle description of a day",
+    args: "<day>",
+    flags: &[
+        Flag::switch("-w", "--wait", "Wait until the puzzle unlocks"),
+        Flag::switch("-s", "--scaffold", "Scaffold the day after downloading it"),
+        Flag::switch(
+            "-P",
+            "--puzzle-only",
+            "Only download the puzzle description",
+        ),
+        Flag::switch("-e", "--encrypt", "Write an encrypted copy of the input"),
+    ],
+    run,
+};
+
+fn time_until_unlock(day: u8, year: u16) -> Option<Duration> {
+    unlock_time(year, day)
+        .duration_since(SystemTime::now())
+        .ok()
 }
 
-fn parse_args() -> Result<Args, pico_args::Error> {
-    let mut args = pico_args::Arguments::from_env();
-    Ok(Args {
-        day: args.free_from_str()?,
-        year: args.opt_value_from_str(["-y", "--year"])?,
-    })
+fn wait_for_unlock(day: u8, year: u16) {
+    while let Some(remaining) = time_until_unlock(day, year) {
+        print!(
+            "\r⏳ Day {day}, {year} unlocks in {}... ",
+            format_duration(remaining + Duration::from_millis(999))
+        );
+        io::stdout().flush().ok();
+        thread::sleep(remaining.min(Duration::from_secs(1)));
+    }
+    println!("\r🔓 Day {day}, {year} is unlocked!                ");
 }
 
-fn main() {
-    let args = match parse_args() {
-        Ok(args) => args,
-        Err(e) => {
-            eprintln!("Failed to process arguments: {e}");
-            process::exit(1);
+fn download_with_retry(day: u8, year: u16) -> Result<(), AocCliError> {
+    let mut attempt = 1;
+    loop {
+        match aoc_cli::download(day, Some(year)) {
+            Ok(_) => return Ok(()),
+            Err(e) if e.is_transient() && attempt < RETRY_ATTEMPTS => {
+                eprintln!("Download failed, retrying in {}s...", RETRY_DELAY.as_secs());
+                thread::sleep(RETRY_DELAY);
+                attempt += 1;
+            }
+            Err(e) => return Err(e),
         }
+    }
+}
+
+/// writes an encrypted copy of the downloaded input that can be committed.
+fn encrypt_input(day: u8) -> Result<(), EncryptionError> {
+    let Some(key) = encryption::load_key()? else {
+        return Err(EncryptionError::MissingKey(encryption::key_path()?));
     };
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+    let input_path = format!("src/inputs/{day:02}.txt");
+    let encrypted = encryption::encrypt_file(&key, Path::new(&input_path))?;
+    println!(
+        "🎄 Successfully wrote encrypted input to \"{}\".",
+        encrypted.display()
+    );
+    Ok(())
+}
+
+pub fn run(global: &Global, matches: &Matches) -> Result<(), CliError> {
+    global.require_text()?;
+    let day = matches.day(0)?;
+
+    if let Err(e) = aoc_cli::check() {
+        eprintln!("{e}");
         process::exit(1);
     }
 
-    match aoc_cli::download(args.day, args.year) {
-        Ok(cmd_output) => {
-            if !cmd_output.status.success() {
+    if matches.flag("--puzzle-only") {
+        match aoc_cli::refresh_puzzle(day, global.year) {
+            Ok(true) => println!(
+                "🎄 Puzzle description changed. Type `cargo read {}` to see what is new.",
+                day
+            ),
+            Ok(false) => println!("Puzzle description is already up to date."),
+            Err(e) => {
+                eprintln!("{e}");
                 process::exit(1);
             }
         }
-        Err(e) => {
-            eprintln!("failed to spawn aoc-cli: {e}");
+        return Ok(());
+    }
+
+    let result = if matches.flag("--wait") {
+        let year = global.year.unwrap_or_else(current_year);
+        wait_for_unlock(day, year);
+        download_with_retry(day, year)
+    } else {
+        let year = global.year.unwrap_or_else(current_event_year);
+        if let Some(remaining) = time_until_unlock(day, year) {
+            eprintln!(
+                "Day {}, {year} unlocks in {}. Append `--wait` to download it once it is available.",
+                day,
+                format_duration(remaining)
+            );
+            process::exit(1);
+        }
+        aoc_cli::download(day, global.year).map(|_| ())
+    };
+
+    if let Err(e) = result {
+        eprintln!("{e}");
+        process::exit(1);
+    }
+
+    if matches.flag("--encrypt") {
+        if let Err(e) = encrypt_input(day) {
+            eprintln!("Failed to encrypt input: {e}");
             process::exit(1);
         }
     }
+
+    if matches.flag("--scaffold") && !run_command(&["scaffold".into(), day.to_string()]) {
+        eprintln!("Failed to scaffold day {day}.");
+        process::exit(1);
+    }
+    Ok(())
 }
@@ -0,0 +1,126 @@
+use crate::cli::{CliError, Command, Global, Matches};
+use advent_of_code::encryption::{self, EncryptionError, Key};
+use advent_of_code::{days, integrity};
+use std::{io, path::PathBuf, process};
+
+enum Action {
+    Generate,
+    Set(Option<String>),
+    Show,
+    Encrypt(Option<u8>),
+}
+
+pub const COMMAND: Command = Command {
+    name: "key",
+    about: "Manage the key used to encrypt inputs",
+    args: "<generate|set [key]|show|encrypt [day]>",
+    flags: &[],
+    run,
+};
+
+fn parse_action(matches: &Matches) -> Result<Action, CliError> {
+    let action = match matches.opt_free::<String>(0, "action")?.as_deref() {
+        Some("generate") => Action::Generate,
+        Some("set") => Action::Set(matches.opt_free(1, "key")?),
+        Some("show") | None => Action::Show,
+        Some("encrypt") => Action::Encrypt(matches.opt_day(1)?),
+        Some(other) => return Err(CliError::InvalidValue("action".into(), other.into())),
+    };
+    Ok(action)
+}
+
+fn input_path(day: u8) -> PathBuf {
+    PathBuf::from(format!("src/inputs/{day:02}.txt"))
+}
+
+fn generate() -> Result<(), EncryptionError> {
+    let path = encryption::key_path()?;
+    if path.exists() {
+        eprintln!(
+            "A key already exists at \"{}\". Remove it first to generate a new one.",
+            path.display()
+        );
+        process::exit(1);
+    }
+
+    let key = Key::generate();
+    encryption::save_key(&key)?;
+    println!("🎄 Saved a new key to \"{}\".", path.display());
+    println!("Share it with your team, e.g. as ${}:", encryption::KEY_VAR);
+    println!("{}", key.to_hex());
+    Ok(())
+}
+
+fn set(key: Option<String>) -> Result<(), EncryptionError> {
+    let key = match key {
+        Some(key) => key,
+        None => {
+            println!("Paste the key of your team and press enter:");
+            let mut line = String::new();
+            io::stdin()
+                .read_line(&mut line)
+                .map_err(EncryptionError::Io)?;
+            line
+        }
+    };
+
+    let path = encryption::save_key(&Key::parse(&key)?)?;
+    println!("🎄 Saved key to \"{}\".", path.display());
+    Ok(())
+}
+
+fn show() -> Result<(), EncryptionError> {
+    match encryption::load_key()? {
+        Some(key) => println!("{}", key.to_hex()),
+        None => {
+            eprintln!(
+                "No key found at \"{}\" or in ${}. Run `cargo key generate` to create one.",
+                encryption::key_path()?.display(),
+                encryption::KEY_VAR
+            );
+            process::exit(1);
+        }
+    }
+    Ok(())
+}
+
+/// encrypts the input of `day`, or every downloaded input if no day was given.
+fn encrypt(day: Option<u8>) -> Result<(), EncryptionError> {
+    let Some(key) = encryption::load_key()? else {
+        return Err(EncryptionError::MissingKey(encryption::key_path()?));
+    };
+
+    let paths: Vec<PathBuf> = match day {
+        Some(day) => vec![input_path(day)],
+        None => days::DAYS
+            .iter()
+            .map(|&day| input_path(day))
+            .filter(|path| path.metadata().is_ok_and(|m| m.len() > 0))
+            .collect(),
+    };
+
+    for path in paths {
+        let encrypted = encryption::encrypt_file(&key, &path)?;
+        integrity::record_checksum(&path).map_err(EncryptionError::Io)?;
+        println!("🎄 Encrypted \"{}\".", encrypted.display());
+    }
+    Ok(())
+}
+
+pub fn run(global: &Global, matches: &Matches) -> Result<(), CliError> {
+    global.require_text()?;
+    let action = parse_action(matches)?;
+
+    let result = match action {
+        Action::Generate => generate(),
+        Action::Set(key) => set(key),
+        Action::Show => show(),
+        Action::Encrypt(day) => encrypt(day),
+    };
+
+    if let Err(e) = result {
+        eprintln!("{e}");
+        process::exit(1);
+    }
+    Ok(())
+}
@@ -0,0 +1,321 @@
+use crate::cli::{CliError, Command as CliCommand, Flag, Global, Matches};
+use advent_of_code::calendar::{current_event_year, format_duration, unlock_time};
+use advent_of_code::session::{self, SessionError};
+use advent_of_code::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use serde::Deserialize;
+use std::{
+    collections::HashMap,
+    fmt, fs,
+    path::PathBuf,
+    process,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+/// adventofcode.com asks to not poll private leaderboards more often than this.
+const CACHE_TTL: Duration = Duration::from_secs(15 * 60);
+const CACHE_DIR: &str = ".cache";
+
+pub const COMMAND: CliCommand = CliCommand {
+    name: "leaderboard",
+    about: "Show the standings of a private leaderboard",
+    args: "[id]",
+    flags: &[
+        Flag::value(
+            "-f",
+            "--file",
+            "path",
+            "Read the leaderboard from a JSON file",
+        ),
+        Flag::switch("-r", "--refresh", "Ignore the cached leaderboard"),
+    ],
+    run,
+};
+
+struct Args {
+    id: Option<u32>,
+    year: Option<u16>,
+    file: Option<PathBuf>,
+    refresh: bool,
+}
+
+#[derive(Deserialize)]
+struct Leaderboard {
+    event: String,
+    members: HashMap<String, Member>,
+}
+
+#[derive(Deserialize)]
+struct Member {
+    id: u32,
+    name: Option<String>,
+    stars: u32,
+    local_score: u32,
+    completion_day_level: HashMap<u8, HashMap<u8, Star>>,
+}
+
+#[derive(Deserialize)]
+struct Star {
+    get_star_ts: u64,
+}
+
+impl Member {
+    fn display_name(&self) -> String {
+        match &self.name {
+            Some(name) => name.clone(),
+            None => format!("(anonymous user #{})", self.id),
+        }
+    }
+
+    fn star_time(&self, day: u8, part: u8) -> Option<SystemTime> {
+        let star = self.completion_day_level.get(&day)?.get(&part)?;
+        Some(UNIX_EPOCH + Duration::from_secs(star.get_star_ts))
+    }
+}
+
+enum LeaderboardError {
+    MissingId,
+    Session(SessionError),
+    FetchFailed(String),
+    Io(std::io::Error),
+    Parse(serde_json::Error),
+}
+
+impl fmt::Display for LeaderboardError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LeaderboardError::MissingId => write!(
+                f,
+                "Need to specify a leaderboard id (as integer). example: `cargo leaderboard 123456`"
+            ),
+            LeaderboardError::Session(e) => write!(f, "{e}"),
+            LeaderboardError::FetchFailed(e) => write!(f, "could not fetch leaderboard: {e}"),
+            LeaderboardError::Io(e) => write!(f, "could not access leaderboard file: {e}"),
+            LeaderboardError::Parse(e) => write!(f, "could not parse leaderboard: {e}"),
+        }
+    }
+}
+
+fn fetch(id: u32, year: u16) -> Result<String, LeaderboardError> {
+    let profile = session::resolve_profile(Some(year));
+    let cookie = session::load(profile.as_deref()).map_err(LeaderboardError::Session)?;
+    let url = format!("https://adventofcode.com/{year}/leaderboard/private/view/{id}.json");
+
+    let output = session::curl(&cookie, &["--fail", "--silent", "--show-error", &url])
+        .map_err(|e| LeaderboardError::FetchFailed(format!("curl could not be called: {e}")))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(LeaderboardError::FetchFailed(stderr.trim().to_string()));
+    }
+
+    String::from_utf8(output.stdout).map_err(|e| LeaderboardError::FetchFailed(e.to_string()))
+}
+
+fn is_fresh(path: &PathBuf) -> bool {
+    fs::metadata(path)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|modified| modified.elapsed().ok())
+        .is_some_and(|age| age < CACHE_TTL)
+}
+
+fn load(args: &Args) -> Result<String, LeaderboardError> {
+    if let Some(file) = &args.file {
+        return fs::read_to_string(file).map_err(LeaderboardError::Io);
+    }
+
+    let id = args.id.ok_or(LeaderboardError::MissingId)?;
+    let year = args.year.unwrap_or_else(current_event_year);
+    let cache_path = PathBuf::from(CACHE_DIR).join(format!("leaderboard-{id}-{year}.json"));
+
+    if !args.refresh && is_fresh(&cache_path) {
+        return fs::read_to_string(&cache_path).map_err(LeaderboardError::Io);
+    }
+
+    match fetch(id, year) {
+        Ok(json) => {
+            fs::create_dir_all(CACHE_DIR).map_err(LeaderboardError::Io)?;
+            fs::write(&cache_path, &json).map_err(LeaderboardError::Io)?;
+            Ok(json)
+        }
+        Err(e) if cache_path.exists() => {
+            eprintln!("{e}");
+            eprintln!(
+                "Falling back to cached leaderboard \"{}\".",
+                cache_path.display()
+            );
+            fs::read_to_string(&cache_path).map_err(LeaderboardError::Io)
+        }
+        Err(e) => Err(e),
+    }
+}
+
+/// orders members by local score, then by stars, then by id.
+fn ranking(leaderboard: &Leaderboard) -> Vec<&Member> {
+    let mut members: Vec<&Member> = leaderboard.members.values().collect();
+    members.sort_by(|a, b| {
+        b.local_score
+            .cmp(&a.local_score)
+            .then(b.stars.cmp(&a.stars))
+            .then(a.id.cmp(&b.id))
+    });
+    members
+}
+
+fn print_standings(leaderboard: &Leaderboard) {
+    let year: u16 = leaderboard
+        .event
+        .parse()
+        .unwrap_or_else(|_| current_event_year());
+
+    let members = ranking(leaderboard);
+
+    println!("🎄 {ANSI_BOLD}Leaderboard {year}{ANSI_RESET} 🎄");
+
+    for (rank, member) in members.iter().enumerate() {
+        println!();
+        println!(
+            "{ANSI_BOLD}{:>3}) {}{ANSI_RESET} {} points, {} ⭐",
+            rank + 1,
+            member.display_name(),
+            member.local_score,
+            member.stars
+        );
+
+        let mut days: Vec<&u8> = member.completion_day_level.keys().collect();
+        days.sort();
+
+        for &day in days {
+            let unlock = unlock_time(year, day);
+            let since_unlock = |t: SystemTime| t.duration_since(unlock).unwrap_or_default();
+
+            let part_one = member.star_time(day, 1);
+            let part_two = member.star_time(day, 2);
+
+            let column = |t: Option<SystemTime>| match t {
+                Some(t) => format_duration(since_unlock(t)),
+                None => "-".to_string(),
+            };
+            let delta = match (part_one, part_two) {
+                (Some(one), Some(two)) => format!(
+                    " {ANSI_ITALIC}(+{}){ANSI_RESET}",
+                    format_duration(two.duration_since(one).unwrap_or_default())
+                ),
+                _ => String::new(),
+            };
+
+            println!(
+                "     Day {day:02}  {:>12}  {:>12}{delta}",
+                column(part_one),
+                column(part_two),
+            );
+        }
+    }
+}
+
+pub fn run(global: &Global, matches: &Matches) -> Result<(), CliError> {
+    global.require_text()?;
+    let args = Args {
+        id: matches.opt_free(0, "id")?,
+        year: global.year,
+        file: matches.value("--file")?,
+        refresh: matches.flag("--refresh"),
+    };
+
+    let leaderboard = load(&args).and_then(|json| {
+        serde_json::from_str::<Leaderboard>(&json).map_err(LeaderboardError::Parse)
+    });
+
+    match leaderboard {
+        Ok(leaderboard) => print_standings(&leaderboard),
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIXTURE: &str = r#"{
+        "event": "2022",
+        "owner_id": 1,
+        "members": {
+            "1": {
+                "id": 1, "name": "Ada", "stars": 3, "local_score": 10, "global_score": 0,
+                "last_star_ts": 1669871000,
+                "completion_day_level": {
+                    "1": {
+                        "1": { "get_star_ts": 1669870900, "star_index": 0 },
+                        "2": { "get_star_ts": 1669871000, "star_index": 1 }
+                    },
+                    "2": { "1": { "get_star_ts": 1669957500, "star_index": 2 } }
+                }
+            },
+            "2": {
+                "id": 2, "name": null, "stars": 4, "local_score": 12, "global_score": 0,
+                "last_star_ts": 1669958000,
+                "completion_day_level": {
+                    "1": {
+                        "1": { "get_star_ts": 1669871200, "star_index": 0 },
+                        "2": { "get_star_ts": 1669871300, "star_index": 1 }
+                    },
+                    "2": {
+                        "1": { "get_star_ts": 1669957600, "star_index": 2 },
+                        "2": { "get_star_ts": 1669958000, "star_index": 3 }
+                    }
+                }
+            },
+            "3": {
+                "id": 3, "name": "Grace", "stars": 4, "local_score": 10, "global_score": 0,
+                "last_star_ts": 1669958100,
+                "completion_day_level": {}
+            },
+            "4": {
+                "id": 4, "name": "Linus", "stars": 3, "local_score": 10, "global_score": 0,
+                "last_star_ts": 0,
+                "completion_day_level": {}
+            }
+        }
+    }"#;
+
+    fn fixture() -> Leaderboard {
+        serde_json::from_str(FIXTURE).unwrap()
+    }
+
+    #[test]
+    fn test_parse() {
+        let leaderboard = fixture();
+        assert_eq!(leaderboard.event, "2022");
+        assert_eq!(leaderboard.members.len(), 4);
+
+        let ada = &leaderboard.members["1"];
+        assert_eq!(ada.display_name(), "Ada");
+        assert_eq!(
+            ada.star_time(1, 2),
+            Some(UNIX_EPOCH + Duration::from_secs(1669871000))
+        );
+        assert_eq!(ada.star_time(2, 2), None);
+        assert_eq!(ada.star_time(3, 1), None);
+
+        assert_eq!(
+            leaderboard.members["2"].display_name(),
+            "(anonymous user #2)"
+        );
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        assert!(serde_json::from_str::<Leaderboard>(r#"{ "event": "2022" }"#).is_err());
+    }
+
+    #[test]
+    fn test_ranking() {
+        let leaderboard = fixture();
+        let ids: Vec<u32> = ranking(&leaderboard).iter().map(|m| m.id).collect();
+        assert_eq!(ids, vec![2, 3, 1, 4]);
+    }
+}
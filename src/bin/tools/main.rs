@@ -0,0 +1,56 @@
+mod all;
+mod bench;
+mod cli;
+mod download;
+mod key;
+mod leaderboard;
+mod read;
+mod scaffold;
+mod session;
+mod solve;
+mod start;
+mod submit;
+
+use cli::{Command, Invocation};
+use std::{env, process};
+
+const COMMANDS: &[&Command] = &[
+    &scaffold::COMMAND,
+    &download::COMMAND,
+    &start::COMMAND,
+    &read::COMMAND,
+    &solve::COMMAND,
+    &all::COMMAND,
+    &bench::COMMAND,
+    &submit::COMMAND,
+    &leaderboard::COMMAND,
+    &session::COMMAND,
+    &key::COMMAND,
+];
+
+fn main() {
+    let invocation = match cli::parse(env::args_os().skip(1).collect(), COMMANDS) {
+        Ok(invocation) => invocation,
+        Err(e) => {
+            eprintln!("Failed to process arguments: {e}");
+            eprintln!("Run `cargo aoc help` for a list of commands.");
+            process::exit(1);
+        }
+    };
+
+    match invocation {
+        Invocation::Help(command) => print!("{}", cli::help(COMMANDS, command)),
+        Invocation::Run(command, global, matches) => match (command.run)(&global, &matches) {
+            Ok(()) => {}
+            Err(e) if e.is_usage() => {
+                eprintln!("Failed to process arguments: {e}");
+                eprintln!("{}", cli::usage(command));
+                process::exit(1);
+            }
+            Err(e) => {
+                eprintln!("{e}");
+                process::exit(1);
+            }
+        },
+    }
+}
@@ -1,46 +1,45 @@
-/*
- * This file contains template code.
- * There is no need to edit this file unless you want to change template functionality.
- */
-use advent_of_code::aoc_cli;
+use crate::cli::{CliError, Command, Flag, Global, Matches};
+use advent_of_code::{aoc_cli, puzzle};
 use std::process;
 
-struct Args {
-    day: u8,
-    year: Option<u16>,
-}
+pub const COMMAND: Command = Command {
+    name: "read",
+    about: "Print the puzzle description of a day",
+    args: "<day>",
+    flags: &[Flag::switch(
+        "-r",
+        "--remote",
+        "Read the description from adventofcode.com",
+    )],
+    run,
+};
 
-fn parse_args() -> Result<Args, pico_args::Error> {
-    let mut args = pico_args::Arguments::from_env();
-    Ok(Args {
-        day: args.free_from_str()?,
-        year: args.opt_value_from_str(["-y", "--year"])?,
-    })
-}
+pub fn run(global: &Global, matches: &Matches) -> Result<(), CliError> {
+    global.require_text()?;
+    let day = matches.day(0)?;
 
-fn main() {
-    let args = match parse_args() {
-        Ok(args) => args,
-        Err(e) => {
-            eprintln!("Failed to process arguments: {e}");
-            process::exit(1);
+    if !matches.flag("--remote") {
+        match puzzle::render(day) {
+            Ok(Some(rendered)) => {
+                println!("{rendered}");
+                return Ok(());
+            }
+            Ok(None) => {}
+            Err(e) => {
+                eprintln!("Failed to read local puzzle description: {e}");
+                process::exit(1);
+            }
         }
-    };
+    }
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+    if let Err(e) = aoc_cli::check() {
+        eprintln!("{e}");
         process::exit(1);
     }
 
-    match aoc_cli::read(args.day, args.year) {
-        Ok(cmd_output) => {
-            if !cmd_output.status.success() {
-                process::exit(1);
-            }
-        }
-        Err(e) => {
-            eprintln!("failed to spawn aoc-cli: {e}");
-            process::exit(1);
-        }
+    if let Err(e) = aoc_cli::read(day, global.year) {
+        eprintln!("{e}");
+        process::exit(1);
     }
+    Ok(())
 }
@@ -1,114 +1,381 @@
-/*
- * This file contains template code.
- * There is no need to edit this file unless you want to change template functionality.
- */
+use crate::cli::{CliError, Command, Flag, Global, Matches};
+use advent_of_code::calendar::current_event_year;
+use advent_of_code::{answers, examples, puzzle, template};
 use std::{
-    fs::{File, OpenOptions},
-    io::Write,
+    collections::HashMap,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
     process,
 };
 
-const MODULE_TEMPLATE: &str = r###"pub fn part_one(input: &str) -> Option<u32> {
-    None
+const DAYS_PATH: &str = "src/days.rs";
+/// start of the list in the registry, the days follow up to the closing `]`.
+const DAYS_START: &str = "DAYS: &[u8] = &[";
+const DEFAULT_TEMPLATE_PATH: &str = "templates/module.rs.tpl";
+
+const DAYS_TEMPLATE: &str = r###"/*
+ * This file is updated by `cargo scaffold`.
+ */
+
+/// days that have a solution module in `src/bin`.
+#[rustfmt::skip]
+pub const DAYS: &[u8] = &[
+{{entries}}];
+"###;
+
+pub const COMMAND: Command = Command {
+    name: "scaffold",
+    about: "Create the module, input and example files of a day",
+    args: "<day>",
+    flags: &[
+        Flag::value(
+            "-t",
+            "--template",
+            "path",
+            "Module template, defaults to `templates/module.rs.tpl`",
+        ),
+        Flag::switch("-f", "--force", "Overwrite an existing module"),
+        Flag::switch("-n", "--dry-run", "Only print what would be changed"),
+        Flag::switch(
+            "-s",
+            "--split-examples",
+            "Create separate example files per part",
+        ),
+    ],
+    run,
+};
+
+struct Args {
+    day: u8,
+    year: Option<u16>,
+    template: PathBuf,
+    force: bool,
+    dry_run: bool,
+    split_examples: bool,
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    None
+fn parse_args(global: &Global, matches: &Matches) -> Result<Args, CliError> {
+    global.require_text()?;
+    Ok(Args {
+        day: matches.day(0)?,
+        year: global.year,
+        template: matches
+            .value("--template")?
+            .unwrap_or_else(|| DEFAULT_TEMPLATE_PATH.into()),
+        force: matches.flag("--force"),
+        dry_run: matches.flag("--dry-run"),
+        split_examples: matches.flag("--split-examples"),
+    })
 }
 
-fn main() {
-    let input = &advent_of_code::read_file("inputs", DAY);
-    advent_of_code::solve!(1, part_one, input);
-    advent_of_code::solve!(2, part_two, input);
+fn render_module(args: &Args) -> Result<String, String> {
+    let template = fs::read_to_string(&args.template).map_err(|e| {
+        format!(
+            "Failed to read module template \"{}\": {e}",
+            args.template.display()
+        )
+    })?;
+
+    let title = puzzle::read_local(args.day)
+        .ok()
+        .flatten()
+        .and_then(|description| puzzle::parse_title(&description))
+        .unwrap_or_else(|| format!("Day {}", args.day));
+
+    let vars = HashMap::from([
+        ("day", args.day.to_string()),
+        ("day_padded", format!("{:02}", args.day)),
+        (
+            "year",
+            args.year.unwrap_or_else(current_event_year).to_string(),
+        ),
+        ("title", title),
+    ]);
+
+    template::render(&template, &vars).map_err(|e| {
+        format!(
+            "Failed to render module template \"{}\": {e}",
+            args.template.display()
+        )
+    })
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+/// spec that runs every example, without checking any answers yet.
+fn render_spec(example_names: &[String]) -> String {
+    let cases: String = example_names
+        .iter()
+        .map(|name| format!("@{name} | - | -\n"))
+        .collect();
+    format!("# <input> | <part one> | <part two>\n{cases}")
+}
 
-    #[test]
-    fn test_part_one() {
-        let input = advent_of_code::read_file("examples", DAY);
-        assert_eq!(part_one(&input), None);
+fn render_days(days: &[u8]) -> String {
+    let entries: String = days.iter().map(|day| format!("    {day},\n")).collect();
+    template::render(DAYS_TEMPLATE, &HashMap::from([("entries", entries)]))
+        .expect("days template should only use known placeholders")
+}
+
+/// days listed in the registry, `days::DAYS` is only up to date for the build of this tool.
+fn parse_days(contents: &str) -> Result<Vec<u8>, io::Error> {
+    let invalid = |message: String| io::Error::new(io::ErrorKind::InvalidData, message);
+
+    let (_, entries) = contents
+        .split_once(DAYS_START)
+        .ok_or_else(|| invalid(format!("could not find `{DAYS_START}`")))?;
+    let (entries, _) = entries
+        .split_once(']')
+        .ok_or_else(|| invalid(format!("could not find the `]` closing `{DAYS_START}`")))?;
+
+    entries
+        .split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .map(|entry| {
+            entry
+                .parse()
+                .map_err(|_| invalid(format!("`{entry}` is not a day")))
+        })
+        .collect()
+}
+
+/// registry with `day` added, `None` if it is already registered.
+fn registered_days(day: u8) -> Result<Option<String>, std::io::Error> {
+    let mut registered = parse_days(&fs::read_to_string(DAYS_PATH)?)?;
+    if registered.contains(&day) {
+        return Ok(None);
     }
 
-    #[test]
-    fn test_part_two() {
-        let input = advent_of_code::read_file("examples", DAY);
-        assert_eq!(part_two(&input), None);
+    registered.push(day);
+    registered.sort_unstable();
+
+    Ok(Some(render_days(&registered)))
+}
+
+/// answers file with empty answers for `day` added, `None` if it already has some.
+fn registered_answers(day: u8) -> Result<Option<String>, std::io::Error> {
+    let contents = fs::read_to_string(answers::ANSWERS_PATH)?;
+    if answers::parse(&contents).contains_key(&(day, 1)) {
+        return Ok(None);
     }
+
+    Ok(Some(contents + &answers::stub(day)))
 }
-"###;
 
-fn parse_args() -> Result<u8, pico_args::Error> {
-    let mut args = pico_args::Arguments::from_env();
-    args.free_from_str()
+enum Action {
+    Create(String),
+    Overwrite(String),
+    Update(String),
+    Keep(&'static str),
+    Conflict,
 }
 
-fn safe_create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new().write(true).create_new(true).open(path)
+struct Step {
+    name: &'static str,
+    path: String,
+    action: Action,
 }
 
-fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new().write(true).create(true).open(path)
+impl Step {
+    fn new(name: &'static str, path: &str, action: Action) -> Self {
+        Step {
+            name,
+            path: path.to_string(),
+            action,
+        }
+    }
+
+    fn describe(&self) -> String {
+        let (verb, note) = match self.action {
+            Action::Create(_) => ("create", ""),
+            Action::Overwrite(_) => ("overwrite", ""),
+            Action::Update(_) => ("update", ""),
+            Action::Keep(reason) => ("keep", reason),
+            Action::Conflict => ("exists", ", append `--force` to overwrite"),
+        };
+        format!("{verb:>9}  \"{}\" ({}{note})", self.path, self.name)
+    }
 }
 
-fn main() {
-    let day = match parse_args() {
-        Ok(day) => day,
-        Err(_) => {
-            eprintln!("Need to specify a day (as integer). example: `cargo scaffold 7`");
-            process::exit(1);
+/// files written so far, with their previous contents, to undo a failed scaffold.
+#[derive(Default)]
+struct Rollback {
+    written: Vec<(String, Option<String>)>,
+}
+
+impl Rollback {
+    fn apply(&mut self, step: &Step) -> Result<(), std::io::Error> {
+        match &step.action {
+            Action::Create(contents) => {
+                let mut file = OpenOptions::new()
+                    .write(true)
+                    .create_new(true)
+                    .open(&step.path)?;
+                self.written.push((step.path.clone(), None));
+                file.write_all(contents.as_bytes())
+            }
+            Action::Overwrite(contents) | Action::Update(contents) => {
+                let previous = fs::read_to_string(&step.path)?;
+                self.written.push((step.path.clone(), Some(previous)));
+                fs::write(&step.path, contents)
+            }
+            Action::Keep(_) | Action::Conflict => Ok(()),
         }
-    };
+    }
 
-    let day_padded = format!("{day:02}");
+    fn undo(self) {
+        for (path, previous) in self.written.into_iter().rev() {
+            let result = match previous {
+                Some(previous) => fs::write(&path, previous),
+                None => fs::remove_file(&path),
+            };
+            match result {
+                Ok(_) => eprintln!("Rolled back \"{path}\""),
+                Err(e) => eprintln!("Failed to roll back \"{path}\": {e}"),
+            }
+        }
+    }
+}
+
+fn plan(args: &Args) -> Result<Vec<Step>, String> {
+    let day_padded = format!("{:02}", args.day);
 
     let input_path = format!("src/inputs/{day_padded}.txt");
-    let example_path = format!("src/examples/{day_padded}.txt");
+    let example_names = if args.split_examples {
+        vec![format!("{day_padded}-1.txt"), format!("{day_padded}-2.txt")]
+    } else {
+        vec![format!("{day_padded}.txt")]
+    };
+    let spec_path = examples::spec_path(args.day);
     let module_path = format!("src/bin/{day_padded}.rs");
 
-    let mut file = match safe_create_file(&module_path) {
-        Ok(file) => file,
-        Err(e) => {
-            eprintln!("Failed to create module file: {e}");
-            process::exit(1);
+    let exists = |path: &str| Path::new(path).exists();
+    let create_or_keep = |path: &str, contents: String| {
+        if exists(path) {
+            Action::Keep(", already exists")
+        } else {
+            Action::Create(contents)
         }
     };
 
-    match file.write_all(MODULE_TEMPLATE.replace("DAY", &day.to_string()).as_bytes()) {
-        Ok(_) => {
-            println!("Created module file \"{}\"", &module_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to write module contents: {e}");
-            process::exit(1);
-        }
+    let module = render_module(args)?;
+    let module_action = match (exists(&module_path), args.force) {
+        (false, _) => Action::Create(module),
+        (true, true) => Action::Overwrite(module),
+        (true, false) => Action::Conflict,
+    };
+
+    let registry =
+        registered_days(args.day).map_err(|e| format!("Failed to read \"{DAYS_PATH}\": {e}"))?;
+    let answers = registered_answers(args.day)
+        .map_err(|e| format!("Failed to read \"{}\": {e}", answers::ANSWERS_PATH))?;
+
+    let mut steps = vec![
+        Step::new("module", &module_path, module_action),
+        Step::new(
+            "input",
+            &input_path,
+            create_or_keep(&input_path, String::new()),
+        ),
+    ];
+    for name in &example_names {
+        let example_path = format!("src/examples/{name}");
+        steps.push(Step::new(
+            "example",
+            &example_path,
+            create_or_keep(&example_path, String::new()),
+        ));
     }
+    steps.extend([
+        Step::new(
+            "example spec",
+            &spec_path,
+            create_or_keep(&spec_path, render_spec(&example_names)),
+        ),
+        Step::new(
+            "registry of days",
+            DAYS_PATH,
+            registry.map_or(Action::Keep(", already registered"), Action::Update),
+        ),
+        Step::new(
+            "answers",
+            answers::ANSWERS_PATH,
+            answers.map_or(Action::Keep(", already registered"), Action::Update),
+        ),
+    ]);
 
-    match create_file(&input_path) {
-        Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
-        }
+    Ok(steps)
+}
+
+pub fn run(global: &Global, matches: &Matches) -> Result<(), CliError> {
+    let args = parse_args(global, matches)?;
+
+    let steps = match plan(&args) {
+        Ok(steps) => steps,
         Err(e) => {
-            eprintln!("Failed to create input file: {e}");
+            eprintln!("{e}");
             process::exit(1);
         }
-    }
+    };
 
-    match create_file(&example_path) {
-        Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
+    if !global.quiet {
+        for step in &steps {
+            println!("{}", step.describe());
         }
-        Err(e) => {
-            eprintln!("Failed to create example file: {e}");
+    }
+
+    if steps
+        .iter()
+        .any(|step| matches!(step.action, Action::Conflict))
+    {
+        eprintln!("---");
+        eprintln!("Scaffold would overwrite existing files, no files were changed.");
+        process::exit(1);
+    }
+
+    if args.dry_run {
+        println!("---");
+        println!("Dry run, no files were changed.");
+        return Ok(());
+    }
+
+    let mut rollback = Rollback::default();
+    for step in &steps {
+        if let Err(e) = rollback.apply(step) {
+            eprintln!("Failed to write {} \"{}\": {e}", step.name, step.path);
+            rollback.undo();
             process::exit(1);
         }
     }
 
-    println!("---");
-    println!(
-        "🎄 Type `cargo solve {}` to run your solution.",
-        &day_padded
-    );
+    if !global.quiet {
+        println!("---");
+        println!(
+            "🎄 Type `cargo solve {:02}` to run your solution.",
+            args.day
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_days() {
+        assert_eq!(
+            parse_days(&render_days(&[1, 2, 25])).unwrap(),
+            vec![1, 2, 25]
+        );
+        assert!(parse_days(&render_days(&[])).unwrap().is_empty());
+
+        // the doc comment above the list must not be mistaken for it.
+        let contents = "/// see [`DAYS`] = &[\"docs\"]\npub const DAYS: &[u8] = &[3, 4];";
+        assert_eq!(parse_days(contents).unwrap(), vec![3, 4]);
+
+        assert!(parse_days("pub const DAYS: &[u16] = &[1];").is_err());
+        assert!(parse_days("pub const DAYS: &[u8] = &[1, 2").is_err());
+        assert!(parse_days("pub const DAYS: &[u8] = &[1, two];").is_err());
+    }
 }
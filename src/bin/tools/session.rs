@@ -0,0 +1,132 @@
+use crate::cli::{CliError, Command, Flag, Global, Matches};
+use advent_of_code::calendar::format_duration;
+use advent_of_code::session::{self, Session, SessionError};
+use std::{io, process};
+
+enum Action {
+    Set(Option<String>),
+    Show,
+    Check,
+    List,
+}
+
+pub const COMMAND: Command = Command {
+    name: "session",
+    about: "Set, show, check or list session cookies",
+    args: "<set [token]|show|check|list>",
+    flags: &[Flag::value(
+        "-p",
+        "--profile",
+        "name",
+        "Use a named profile instead of the default session",
+    )],
+    run,
+};
+
+fn parse_action(matches: &Matches) -> Result<Action, CliError> {
+    let action = match matches.opt_free::<String>(0, "action")?.as_deref() {
+        Some("set") => Action::Set(matches.opt_free(1, "token")?),
+        Some("show") | None => Action::Show,
+        Some("check") => Action::Check,
+        Some("list") => Action::List,
+        Some(other) => return Err(CliError::InvalidValue("action".into(), other.into())),
+    };
+    Ok(action)
+}
+
+fn print_expiry(session: &Session) {
+    match session.remaining() {
+        Some(remaining) if session.is_expiring() => eprintln!(
+            "⚠️  Session was saved {} ago and expires in about {}. Consider refreshing it.",
+            format_duration(session::SESSION_LIFETIME - remaining),
+            format_duration(remaining)
+        ),
+        Some(remaining) => println!("Expires in about {}.", format_duration(remaining)),
+        None => {}
+    }
+}
+
+fn set(profile: Option<&str>, token: Option<String>) -> Result<(), SessionError> {
+    let token = match token {
+        Some(token) => token,
+        None => {
+            println!("Paste your session cookie and press enter:");
+            let mut line = String::new();
+            io::stdin().read_line(&mut line).map_err(SessionError::Io)?;
+            line
+        }
+    };
+
+    let path = session::save(profile, &token)?;
+    println!("🎄 Saved session cookie to \"{}\".", path.display());
+    Ok(())
+}
+
+fn show(profile: Option<&str>) -> Result<(), SessionError> {
+    let session = session::load(profile)?;
+    match &session.path {
+        Some(path) => println!("Session from \"{}\":", path.display()),
+        None => println!("Session from ${}:", session::SESSION_VAR),
+    }
+    println!("{}", session.redacted());
+    print_expiry(&session);
+    Ok(())
+}
+
+fn check(profile: Option<&str>) -> Result<bool, SessionError> {
+    let session = session::load(profile)?;
+    let is_valid = session::validate(&session)?;
+
+    if is_valid {
+        println!("🎄 Session {} is valid.", session.redacted());
+        print_expiry(&session);
+    } else {
+        eprintln!(
+            "Session {} was rejected by adventofcode.com. It has probably expired, run `cargo session set` with a fresh cookie.",
+            session.redacted()
+        );
+    }
+
+    Ok(is_valid)
+}
+
+fn list() -> Result<(), SessionError> {
+    let default = session::session_path(None)?;
+    if default.exists() {
+        println!("(default) \"{}\"", default.display());
+    }
+
+    for profile in session::list_profiles()? {
+        println!(
+            "{profile} \"{}\"",
+            session::session_path(Some(&profile))?.display()
+        );
+    }
+    Ok(())
+}
+
+pub fn run(global: &Global, matches: &Matches) -> Result<(), CliError> {
+    global.require_text()?;
+    let action = parse_action(matches)?;
+    let profile = matches
+        .value::<String>("--profile")?
+        .or_else(|| session::resolve_profile(global.year));
+    let profile = profile.as_deref();
+
+    let result = match action {
+        Action::Set(token) => set(profile, token),
+        Action::Show => show(profile),
+        Action::Check => check(profile).map(|is_valid| {
+            if !is_valid {
+                process::exit(1);
+            }
+        }),
+        Action::List => list(),
+    };
+
+    if let Err(e) = result {
+        eprintln!("{e}");
+        process::exit(1);
+    }
+    Ok(())
+}
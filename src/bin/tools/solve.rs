@@ -0,0 +1,130 @@
+use crate::cli::{CliError, Command, Flag, Format, Global, Matches};
+use advent_of_code::{export, parse_results, visualise, PartResult};
+use serde_json::json;
+use std::{
+    io,
+    process::{self, Command as Process, Stdio},
+};
+
+pub const COMMAND: Command = Command {
+    name: "solve",
+    about: "Run the solution of a day",
+    args: "<day>",
+    flags: &[
+        Flag::switch("-r", "--release", "Build in release mode"),
+        Flag::switch(
+            "-v",
+            "--visualise",
+            "Animate the puzzle state in the terminal, if the solution supports it",
+        ),
+        Flag::value(
+            "-f",
+            "--fps",
+            "frames",
+            "Frames per second of the animation, defaults to 10",
+        ),
+        Flag::value(
+            "-e",
+            "--export",
+            "gif|png",
+            "Save the animation to target/visualisations, if the solution supports it",
+        ),
+    ],
+    run,
+};
+
+fn cargo_args(day: &str, release: bool) -> Vec<&str> {
+    let mut args = vec!["run", "--bin", day];
+    if release {
+        args.push("--release");
+    }
+    args
+}
+
+/// runs the solution of `day` and returns what it printed.
+/// stderr is only passed on if `show_errors` is set, `all` hides panics of unsolved days.
+pub fn run_day(day: u8, release: bool, show_errors: bool) -> io::Result<String> {
+    let day = format!("{day:02}");
+    let output = Process::new("cargo")
+        .arg("--quiet")
+        .args(cargo_args(&day, release))
+        .stderr(if show_errors {
+            Stdio::inherit()
+        } else {
+            Stdio::null()
+        })
+        .output()?;
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// `01.1 = 24000` lines, in the format of `src/answers.txt`.
+pub fn answer_lines(day: u8, results: &[PartResult]) -> String {
+    results
+        .iter()
+        .map(|result| {
+            let answer = result.answer.as_deref().unwrap_or_default();
+            format!(
+                "{day:02}.{} = {}\n",
+                result.part,
+                answer.replace('\n', "\\n")
+            )
+        })
+        .collect()
+}
+
+fn run(global: &Global, matches: &Matches) -> Result<(), CliError> {
+    let day = matches.day(0)?;
+    let release = matches.flag("--release");
+    let visualise = matches.flag("--visualise");
+    let fps: Option<u32> = matches.value("--fps")?;
+    if fps == Some(0) {
+        return Err(CliError::InvalidValue("--fps".into(), "0".into()));
+    }
+    if visualise && global.quiet {
+        return Err(CliError::Unsupported("`--visualise` with `--quiet`"));
+    }
+    let export: Option<String> = matches.value("--export")?;
+    if let Some(format) = export.as_deref().filter(|f| !["gif", "png"].contains(f)) {
+        return Err(CliError::InvalidValue("--export".into(), format.into()));
+    }
+    if export.is_some() && global.quiet {
+        return Err(CliError::Unsupported("`--export` with `--quiet`"));
+    }
+    if visualise || export.is_some() {
+        global.require_text()?;
+    }
+
+    if global.format == Format::Text && !global.quiet {
+        let mut cargo = Process::new("cargo");
+        cargo.args(cargo_args(&format!("{day:02}"), release));
+        if visualise {
+            cargo
+                .args(["--features", "visualise"])
+                .env(visualise::ENABLE_VAR, "1");
+            if let Some(fps) = fps {
+                cargo.env(visualise::FPS_VAR, fps.to_string());
+            }
+        }
+        if let Some(format) = &export {
+            cargo
+                .args(["--features", "export"])
+                .env(export::EXPORT_VAR, format);
+        }
+        let status = cargo.status();
+        match status {
+            Ok(status) if status.success() => return Ok(()),
+            Ok(status) => process::exit(status.code().unwrap_or(1)),
+            Err(e) => return Err(CliError::Failed(format!("Failed to run cargo: {e}"))),
+        }
+    }
+
+    let output = run_day(day, release, true)
+        .map_err(|e| CliError::Failed(format!("Failed to run cargo: {e}")))?;
+    let results = parse_results(&output);
+
+    match global.format {
+        Format::Json => println!("{:#}", json!({ "day": day, "parts": results })),
+        Format::Text => print!("{}", answer_lines(day, &results)),
+    }
+    Ok(())
+}
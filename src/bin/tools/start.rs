@@ -0,0 +1,96 @@
+use crate::cli::{run_command, CliError, Command, Global, Matches};
+use advent_of_code::{aoc_cli, puzzle};
+use std::{fs, path::Path, process};
+
+pub const COMMAND: Command = Command {
+    name: "start",
+    about: "Download a day, extract its example and scaffold it",
+    args: "<day>",
+    flags: &[],
+    run,
+};
+
+struct Args {
+    day: u8,
+    year: Option<u16>,
+}
+
+fn is_missing_or_empty(path: &str) -> bool {
+    fs::metadata(path).map_or(true, |m| m.len() == 0)
+}
+
+fn download(args: &Args) -> Result<(), String> {
+    let input_path = format!("src/inputs/{:02}.txt", args.day);
+    let puzzle_path = puzzle::puzzle_path(args.day);
+
+    if !is_missing_or_empty(&input_path) && !is_missing_or_empty(&puzzle_path) {
+        println!("Using downloaded input \"{input_path}\" and puzzle \"{puzzle_path}\"");
+        return Ok(());
+    }
+
+    aoc_cli::check().map_err(|e| e.to_string())?;
+    aoc_cli::download(args.day, args.year)
+        .map(|_| ())
+        .map_err(|e| e.to_string())
+}
+
+fn write_example(day: u8) -> Result<(), String> {
+    let example_path = format!("src/examples/{day:02}.txt");
+    if !is_missing_or_empty(&example_path) {
+        println!("Using existing example \"{example_path}\"");
+        return Ok(());
+    }
+
+    let description = puzzle::read_local(day)
+        .map_err(|e| format!("Failed to read puzzle description: {e}"))?
+        .unwrap_or_default();
+
+    match puzzle::extract_example(&description) {
+        Some(example) => {
+            fs::write(&example_path, example)
+                .map_err(|e| format!("Failed to write example file: {e}"))?;
+            println!("Extracted example to \"{example_path}\"");
+        }
+        None => println!("Found no example in the puzzle description, paste it yourself."),
+    }
+    Ok(())
+}
+
+fn scaffold(args: &Args) -> Result<(), String> {
+    if Path::new(&format!("src/bin/{:02}.rs", args.day)).exists() {
+        println!(
+            "Module for day {} already exists, skipping scaffold.",
+            args.day
+        );
+        return Ok(());
+    }
+
+    let mut cmd_args = vec!["scaffold".to_string(), args.day.to_string()];
+    if let Some(year) = args.year {
+        cmd_args.extend(["--year".into(), year.to_string()]);
+    }
+
+    if run_command(&cmd_args) {
+        Ok(())
+    } else {
+        Err(format!("Failed to scaffold day {}.", args.day))
+    }
+}
+
+pub fn run(global: &Global, matches: &Matches) -> Result<(), CliError> {
+    global.require_text()?;
+    let args = Args {
+        day: matches.day(0)?,
+        year: global.year,
+    };
+
+    let result = download(&args)
+        .and_then(|_| write_example(args.day))
+        .and_then(|_| scaffold(&args));
+
+    if let Err(e) = result {
+        eprintln!("{e}");
+        process::exit(1);
+    }
+    Ok(())
+}
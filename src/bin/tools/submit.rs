@@ -0,0 +1,42 @@
+use crate::cli::{CliError, Command, Global, Matches};
+use advent_of_code::aoc_cli;
+
+pub const COMMAND: Command = Command {
+    name: "submit",
+    about: "Submit an answer, e.g. `cargo submit 1 2 45000` for part 2 of day 1",
+    args: "<day> <part> <answer>",
+    flags: &[],
+    run,
+};
+
+pub fn run(global: &Global, matches: &Matches) -> Result<(), CliError> {
+    global.require_text()?;
+    let day = matches.day(0)?;
+    let part: u8 = matches.free(1, "part")?;
+    if part != 1 && part != 2 {
+        return Err(CliError::InvalidValue("part".into(), part.to_string()));
+    }
+    let answer: String = matches.free(2, "answer")?;
+
+    let failed = |e: aoc_cli::AocCliError| CliError::Failed(e.to_string());
+    aoc_cli::check().map_err(failed)?;
+    aoc_cli::submit(day, global.year, part, &answer).map_err(failed)?;
+
+    // an accepted answer adds to the puzzle description, a rejected one leaves it unchanged.
+    match aoc_cli::refresh_puzzle(day, global.year) {
+        Ok(true) => {
+            println!("---");
+            println!(
+                "🎄 Puzzle description updated. Type `cargo read {}` to see what is new.",
+                day
+            );
+        }
+        Ok(false) => {}
+        Err(e) => {
+            return Err(CliError::Failed(format!(
+                "Failed to refresh puzzle description: {e}"
+            )))
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,115 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+const SECS_PER_DAY: u64 = 24 * 60 * 60;
+
+/// puzzles unlock at midnight EST (UTC-5), which is 05:00 UTC.
+const UNLOCK_OFFSET_SECS: u64 = 5 * 60 * 60;
+
+/// moment the puzzle for `day` of the `year` event becomes available.
+pub fn unlock_time(year: u16, day: u8) -> SystemTime {
+    let days = days_from_civil(year.into(), 12, day.into());
+    UNIX_EPOCH + Duration::from_secs(days * SECS_PER_DAY + UNLOCK_OFFSET_SECS)
+}
+
+/// year of the most recent event that has started, as seen from `now`.
+pub fn event_year(now: SystemTime) -> u16 {
+    let (year, month, _) = unlock_timezone_date(now);
+
+    if month == 12 {
+        year
+    } else {
+        year - 1
+    }
+}
+
+pub fn current_event_year() -> u16 {
+    event_year(SystemTime::now())
+}
+
+/// calendar year in the timezone puzzles unlock in.
+pub fn current_year() -> u16 {
+    let (year, _, _) = unlock_timezone_date(SystemTime::now());
+    year
+}
+
+fn unlock_timezone_date(time: SystemTime) -> (u16, u8, u8) {
+    let secs = time
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    civil_from_days(secs.saturating_sub(UNLOCK_OFFSET_SECS) / SECS_PER_DAY)
+}
+
+/// formats a duration as `HH:MM:SS`, prefixed with whole days if there are any.
+pub fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    let (days, secs) = (secs / SECS_PER_DAY, secs % SECS_PER_DAY);
+    let time = format!(
+        "{:02}:{:02}:{:02}",
+        secs / 3600,
+        secs % 3600 / 60,
+        secs % 60
+    );
+
+    if days > 0 {
+        format!("{days}d {time}")
+    } else {
+        time
+    }
+}
+
+/// days since 1970-01-01 for a date in the proleptic gregorian calendar.
+/// see: http://howardhinnant.github.io/date_algorithms.html#days_from_civil
+fn days_from_civil(year: u64, month: u64, day: u64) -> u64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year / 400;
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// inverse of `days_from_civil`.
+/// see: http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+fn civil_from_days(days: u64) -> (u16, u8, u8) {
+    let days = days + 719_468;
+    let era = days / 146_097;
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + u64::from(month <= 2);
+    (year as u16, month as u8, day as u8)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unlock_time() {
+        // 2022-12-01T05:00:00Z
+        assert_eq!(
+            unlock_time(2022, 1),
+            UNIX_EPOCH + Duration::from_secs(1_669_870_800)
+        );
+        assert_eq!(civil_from_days(days_from_civil(2024, 2, 29)), (2024, 2, 29));
+    }
+
+    #[test]
+    fn test_event_year() {
+        let before_unlock = unlock_time(2022, 1) - Duration::from_secs(1);
+        assert_eq!(event_year(before_unlock), 2021);
+        assert_eq!(event_year(unlock_time(2022, 1)), 2022);
+        assert_eq!(event_year(unlock_time(2022, 25)), 2022);
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(Duration::from_secs(754)), "00:12:34");
+        assert_eq!(format_duration(Duration::from_secs(90_061)), "1d 01:01:01");
+    }
+}
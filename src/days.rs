@@ -0,0 +1,19 @@
+/*
+ * This file is updated by `cargo scaffold`.
+ */
+
+/// days that have a solution module in `src/bin`.
+#[rustfmt::skip]
+pub const DAYS: &[u8] = &[
+    1,
+    2,
+    3,
+    4,
+    5,
+    6,
+    7,
+    8,
+    9,
+    10,
+    11,
+];
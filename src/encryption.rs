@@ -0,0 +1,223 @@
+use crate::session::{home_dir, write_private};
+use chacha20poly1305::{
+    aead::{Aead, AeadCore, KeyInit, OsRng, Payload},
+    ChaCha20Poly1305, Nonce,
+};
+use std::{
+    env,
+    error::Error,
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+/// key that takes precedence over the key file, e.g. for CI.
+pub const KEY_VAR: &str = "AOC_INPUT_KEY";
+/// encrypted files start with this, to tell them apart from later formats.
+const MAGIC: &[u8] = b"aoc-enc1";
+const NONCE_LEN: usize = 12;
+
+#[derive(Debug)]
+pub enum EncryptionError {
+    HomeNotFound,
+    MissingKey(PathBuf),
+    InvalidKey,
+    InvalidFile,
+    Io(io::Error),
+}
+
+impl Display for EncryptionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EncryptionError::HomeNotFound => write!(f, "could not determine home directory."),
+            EncryptionError::MissingKey(path) => write!(
+                f,
+                "file is encrypted, but there is no key at \"{}\" or in ${KEY_VAR}. Run `cargo key set <key>` to add one.",
+                path.display()
+            ),
+            EncryptionError::InvalidKey => {
+                write!(f, "key must be 64 hexadecimal characters (32 bytes).")
+            }
+            EncryptionError::InvalidFile => write!(
+                f,
+                "file could not be decrypted, it is damaged, was encrypted with another key or belongs to another input."
+            ),
+            EncryptionError::Io(_) => write!(f, "could not access key or encrypted file."),
+        }
+    }
+}
+
+impl Error for EncryptionError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            EncryptionError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+/// symmetric key shared by a team to commit encrypted inputs.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Key([u8; 32]);
+
+impl Key {
+    pub fn generate() -> Self {
+        Key(ChaCha20Poly1305::generate_key(&mut OsRng).into())
+    }
+
+    pub fn parse(hex: &str) -> Result<Self, EncryptionError> {
+        let hex = hex.trim();
+        if hex.len() != 64 || !hex.is_ascii() {
+            return Err(EncryptionError::InvalidKey);
+        }
+
+        let mut key = [0; 32];
+        for (byte, pair) in key.iter_mut().zip(hex.as_bytes().chunks(2)) {
+            let pair = std::str::from_utf8(pair).map_err(|_| EncryptionError::InvalidKey)?;
+            *byte = u8::from_str_radix(pair, 16).map_err(|_| EncryptionError::InvalidKey)?;
+        }
+        Ok(Key(key))
+    }
+
+    pub fn to_hex(&self) -> String {
+        self.0.iter().map(|byte| format!("{byte:02x}")).collect()
+    }
+
+    fn cipher(&self) -> ChaCha20Poly1305 {
+        ChaCha20Poly1305::new(&self.0.into())
+    }
+}
+
+pub fn key_path() -> Result<PathBuf, EncryptionError> {
+    home_dir()
+        .map(|home| home.join(".adventofcode.key"))
+        .map_err(|_| EncryptionError::HomeNotFound)
+}
+
+/// key from `$AOC_INPUT_KEY` or the key file, `None` if neither is set.
+pub fn load_key() -> Result<Option<Key>, EncryptionError> {
+    if let Ok(key) = env::var(KEY_VAR) {
+        return Key::parse(&key).map(Some);
+    }
+
+    match fs::read_to_string(key_path()?) {
+        Ok(key) => Key::parse(&key).map(Some),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(EncryptionError::Io(e)),
+    }
+}
+
+pub fn save_key(key: &Key) -> Result<PathBuf, EncryptionError> {
+    let path = key_path()?;
+    write_private(&path, key.to_hex().as_bytes()).map_err(EncryptionError::Io)?;
+    Ok(path)
+}
+
+/// `src/inputs/01.txt` is stored encrypted as `src/inputs/01.txt.enc`.
+pub fn encrypted_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(".enc");
+    PathBuf::from(name)
+}
+
+/// encrypts `plaintext`, bound to `name` so it can not be passed off as another file.
+pub fn encrypt(key: &Key, name: &str, plaintext: &[u8]) -> Vec<u8> {
+    let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let payload = Payload {
+        msg: plaintext,
+        aad: name.as_bytes(),
+    };
+    let ciphertext = key
+        .cipher()
+        .encrypt(&nonce, payload)
+        .expect("encrypting in memory should not fail");
+
+    [MAGIC, nonce.as_slice(), &ciphertext].concat()
+}
+
+/// decrypts `contents`, which have to be encrypted for `name`.
+pub fn decrypt(key: &Key, name: &str, contents: &[u8]) -> Result<Vec<u8>, EncryptionError> {
+    let contents = contents
+        .strip_prefix(MAGIC)
+        .filter(|rest| rest.len() >= NONCE_LEN)
+        .ok_or(EncryptionError::InvalidFile)?;
+    let (nonce, ciphertext) = contents.split_at(NONCE_LEN);
+
+    let payload = Payload {
+        msg: ciphertext,
+        aad: name.as_bytes(),
+    };
+    key.cipher()
+        .decrypt(Nonce::from_slice(nonce), payload)
+        .map_err(|_| EncryptionError::InvalidFile)
+}
+
+/// writes an encrypted copy of the file at `path` next to it.
+pub fn encrypt_file(key: &Key, path: &Path) -> Result<PathBuf, EncryptionError> {
+    let plaintext = fs::read(path).map_err(EncryptionError::Io)?;
+    let encrypted = encrypted_path(path);
+    fs::write(&encrypted, encrypt(key, &file_name(path), &plaintext))
+        .map_err(EncryptionError::Io)?;
+    Ok(encrypted)
+}
+
+/// contents of the encrypted copy of `path`, `None` if there is none.
+pub fn read_encrypted(path: &Path) -> Result<Option<Vec<u8>>, EncryptionError> {
+    let encrypted = encrypted_path(path);
+    if !encrypted.exists() {
+        return Ok(None);
+    }
+
+    let key = match load_key()? {
+        Some(key) => key,
+        None => return Err(EncryptionError::MissingKey(key_path()?)),
+    };
+    let contents = fs::read(&encrypted).map_err(EncryptionError::Io)?;
+    decrypt(&key, &file_name(path), &contents).map(Some)
+}
+
+/// name the contents of `path` are encrypted for, e.g. `01.txt`.
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .unwrap_or_default()
+        .to_string_lossy()
+        .into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_key_hex() {
+        let key = Key::generate();
+        assert_eq!(Key::parse(&key.to_hex()).unwrap(), key);
+
+        assert!(Key::parse("00ff").is_err());
+        assert!(Key::parse(&"zz".repeat(32)).is_err());
+    }
+
+    #[test]
+    fn test_encrypt() {
+        let key = Key::generate();
+        let encrypted = encrypt(&key, "01.txt", b"1000\n2000\n");
+
+        assert_ne!(encrypt(&key, "01.txt", b"1000\n2000\n"), encrypted);
+        assert_eq!(
+            decrypt(&key, "01.txt", &encrypted).unwrap(),
+            b"1000\n2000\n"
+        );
+
+        assert!(decrypt(&Key::generate(), "01.txt", &encrypted).is_err());
+        assert!(decrypt(&key, "02.txt", &encrypted).is_err());
+        assert!(decrypt(&key, "01.txt", b"1000\n2000\n").is_err());
+    }
+
+    #[test]
+    fn test_encrypted_path() {
+        assert_eq!(
+            encrypted_path(Path::new("src/inputs/01.txt")),
+            PathBuf::from("src/inputs/01.txt.enc")
+        );
+    }
+}
@@ -0,0 +1,173 @@
+use crate::integrity;
+use std::{env, fmt::Display, fs};
+
+/// one line of a spec file: an input and the answers expected for it.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Case {
+    pub line: usize,
+    pub input: Input,
+    /// expected answers for part one and two, `None` if that part is not checked.
+    pub expected: [Option<String>; 2],
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Input {
+    /// name of a file in `src/examples`.
+    File(String),
+    Inline(String),
+}
+
+impl Input {
+    fn read(&self) -> String {
+        match self {
+            Input::File(name) => {
+                let path = env::current_dir().unwrap().join("src/examples").join(name);
+                integrity::read(&path).unwrap_or_else(|e| {
+                    panic!("could not open example \"{}\": {e}", path.display())
+                })
+            }
+            Input::Inline(input) => input.clone(),
+        }
+    }
+}
+
+impl Display for Input {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Input::File(name) => write!(f, "@{name}"),
+            Input::Inline(input) => write!(f, "`{}`", input.replace('\n', "\\n")),
+        }
+    }
+}
+
+pub fn spec_path(day: u8) -> String {
+    format!("src/examples/{day:02}.spec")
+}
+
+fn unescape(value: &str) -> String {
+    value.trim().replace("\\n", "\n")
+}
+
+/// parses a spec file with one `<input> | <part one> | <part two>` case per line.
+pub fn parse(spec: &str) -> Result<Vec<Case>, String> {
+    spec.lines()
+        .enumerate()
+        .map(|(idx, line)| (idx + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+        .map(|(line_nr, line)| {
+            // split from the right, inputs might contain `|` themselves.
+            let mut fields = line.rsplitn(3, '|');
+            let (Some(part_two), Some(part_one), Some(input)) =
+                (fields.next(), fields.next(), fields.next())
+            else {
+                return Err(format!(
+                    "line {line_nr}: expected `<input> | <part one> | <part two>`"
+                ));
+            };
+
+            let input = input.trim();
+            let input = match input.strip_prefix('@') {
+                Some(name) => Input::File(name.to_string()),
+                None => Input::Inline(unescape(input)),
+            };
+
+            let expected = [part_one, part_two].map(|answer| {
+                let answer = answer.trim();
+                (answer != "-").then(|| unescape(answer))
+            });
+
+            Ok(Case {
+                line: line_nr,
+                input,
+                expected,
+            })
+        })
+        .collect()
+}
+
+/// runs `solver` on every case in the spec file of `day` that has an answer for `part`,
+/// panicking with a list of all failed cases.
+pub fn check<A: Display>(day: u8, part: u8, solver: impl Fn(&str) -> Option<A>) {
+    let path = spec_path(day);
+    let spec = fs::read_to_string(env::current_dir().unwrap().join(&path))
+        .unwrap_or_else(|e| panic!("could not open spec \"{path}\": {e}"));
+    let cases = parse(&spec).unwrap_or_else(|e| panic!("invalid spec \"{path}\": {e}"));
+
+    // cases without an answer for this part might not even be valid inputs for it.
+    let cases: Vec<_> = cases
+        .iter()
+        .filter_map(|case| Some((case, case.expected[usize::from(part) - 1].as_ref()?)))
+        .collect();
+
+    let mut failures = Vec::new();
+    for &(case, expected) in &cases {
+        let result = solver(&case.input.read()).map(|r| r.to_string());
+        if result.as_ref() != Some(expected) {
+            failures.push(format!(
+                "{path}:{}: part {part} of {}\n  expected: {expected}\n       got: {}",
+                case.line,
+                case.input,
+                result.unwrap_or_else(|| "not solved.".into())
+            ));
+        }
+    }
+
+    assert!(
+        failures.is_empty(),
+        "{} of {} example cases of part {part} failed:\n{}",
+        failures.len(),
+        cases.len(),
+        failures.join("\n")
+    );
+}
+
+/// generates a test per part that checks a day's solutions against its spec file.
+#[macro_export]
+macro_rules! example_tests {
+    ($day:expr) => {
+        #[cfg(test)]
+        mod example_tests {
+            #[test]
+            fn part_one() {
+                $crate::examples::check($day, 1, super::part_one);
+            }
+
+            #[test]
+            fn part_two() {
+                $crate::examples::check($day, 2, super::part_two);
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let spec = "# comment\n@04.txt | 2 | 4\n\n2-8,3-7 | 1 | -\na|b\\nc | x\\ny | 1";
+
+        assert_eq!(
+            parse(spec),
+            Ok(vec![
+                Case {
+                    line: 2,
+                    input: Input::File("04.txt".into()),
+                    expected: [Some("2".into()), Some("4".into())],
+                },
+                Case {
+                    line: 4,
+                    input: Input::Inline("2-8,3-7".into()),
+                    expected: [Some("1".into()), None],
+                },
+                Case {
+                    line: 5,
+                    input: Input::Inline("a|b\nc".into()),
+                    expected: [Some("x\ny".into()), Some("1".into())],
+                },
+            ])
+        );
+        assert!(parse("missing answers").is_err());
+    }
+}
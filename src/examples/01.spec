@@ -0,0 +1,2 @@
+# <input> | <part one> | <part two>
+@01.txt | 24000 | 45000
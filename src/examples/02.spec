@@ -0,0 +1,2 @@
+# <input> | <part one> | <part two>
+@02.txt | 15 | 12
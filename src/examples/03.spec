@@ -0,0 +1,2 @@
+# <input> | <part one> | <part two>
+@03.txt | 157 | 70
@@ -0,0 +1,6 @@
+# <input> | <part one> | <part two>
+@04.txt | 2 | 4
+3-7,2-8 | 1 | 1
+2-8,3-7 | 1 | 1
+5-7,7-9 | 0 | 1
+2-4,6-8 | 0 | 0
@@ -0,0 +1,2 @@
+# <input> | <part one> | <part two>
+@05.txt | CMZ | MCD
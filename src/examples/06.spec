@@ -0,0 +1,2 @@
+# <input> | <part one> | <part two>
+@06.txt | 7 | 19
@@ -0,0 +1,2 @@
+# <input> | <part one> | <part two>
+@07.txt | 95437 | 24933642
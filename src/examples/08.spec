@@ -0,0 +1,2 @@
+# <input> | <part one> | <part two>
+@08.txt | 21 | 8
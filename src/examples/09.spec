@@ -0,0 +1,3 @@
+# <input> | <part one> | <part two>
+@09-1.txt | 13 | 1
+@09-2.txt | - | 36
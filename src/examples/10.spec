@@ -0,0 +1,2 @@
+# <input> | <part one> | <part two>
+@10.txt | 13140 | ##..##..##..##..##..##..##..##..##..##..\n###...###...###...###...###...###...###.\n####....####....####....####....####....\n#####.....#####.....#####.....#####.....\n######......######......######......####\n#######.......#######.......#######.....
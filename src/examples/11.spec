@@ -0,0 +1,2 @@
+# <input> | <part one> | <part two>
+@11.txt | 10605 | 2713310158
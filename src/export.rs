@@ -0,0 +1,518 @@
+//! Saves animations of puzzle states as GIFs or PNGs.
+//!
+//! States implement [`Export`] to draw themselves, and `Runner::export` records every step of a `Simulation`.
+//! Other states can be captured into a [`Recording`] that is saved when it is done. Frames are only kept with
+//! the `export` feature and if `AOC_EXPORT` is set to `gif` or `png`, which `cargo solve <day> --export <gif|png>`
+//! does. Otherwise capturing does nothing and states are never drawn.
+
+use crate::helpers::{Grid, Point2};
+use std::{
+    env,
+    error::Error,
+    fmt::{self, Display},
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+/// the format to save recordings in, `gif` or `png`.
+pub const EXPORT_VAR: &str = "AOC_EXPORT";
+/// where recordings are saved, relative to the root of the repository.
+pub const EXPORT_DIR: &str = "target/visualisations";
+
+pub type Rgb = [u8; 3];
+
+pub const BLACK: Rgb = [0, 0, 0];
+pub const WHITE: Rgb = [255, 255, 255];
+
+#[derive(Debug)]
+pub enum ExportError {
+    /// the `export` feature is disabled.
+    Disabled,
+    Empty,
+    TooLarge {
+        width: usize,
+        height: usize,
+    },
+    IoError(io::Error),
+    #[cfg(feature = "export")]
+    PngError(png::EncodingError),
+    #[cfg(feature = "export")]
+    GifError(gif::EncodingError),
+}
+
+impl Display for ExportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExportError::Disabled => write!(
+                f,
+                "saving images needs the `export` feature, e.g. `cargo solve <day> --export gif`."
+            ),
+            ExportError::Empty => write!(f, "there are no frames to save."),
+            ExportError::TooLarge { width, height } => {
+                write!(f, "{width}x{height} pixels is too large for a GIF.")
+            }
+            ExportError::IoError(e) => write!(f, "could not write image: {e}"),
+            #[cfg(feature = "export")]
+            ExportError::PngError(e) => write!(f, "could not encode PNG: {e}"),
+            #[cfg(feature = "export")]
+            ExportError::GifError(e) => write!(f, "could not encode GIF: {e}"),
+        }
+    }
+}
+
+impl Error for ExportError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ExportError::IoError(e) => Some(e),
+            #[cfg(feature = "export")]
+            ExportError::PngError(e) => Some(e),
+            #[cfg(feature = "export")]
+            ExportError::GifError(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for ExportError {
+    fn from(e: io::Error) -> Self {
+        ExportError::IoError(e)
+    }
+}
+
+/// an RGB image, stored row by row.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+    width: usize,
+    height: usize,
+    pixels: Vec<Rgb>,
+}
+
+impl Image {
+    pub fn new(width: usize, height: usize, background: Rgb) -> Self {
+        Image {
+            width,
+            height,
+            pixels: vec![background; width * height],
+        }
+    }
+
+    /// one pixel per cell of `grid`, coloured by `colour`.
+    pub fn from_grid<T>(grid: &Grid<T>, colour: impl FnMut(&T) -> Rgb) -> Self {
+        Image {
+            width: grid.width(),
+            height: grid.height(),
+            pixels: grid.iter().map(|(_, cell)| cell).map(colour).collect(),
+        }
+    }
+
+    /// the area from `min` to `max`, both included, with one pixel per point. points outside of it are left out.
+    pub fn from_points(
+        points: impl IntoIterator<Item = (Point2, Rgb)>,
+        (min, max): (Point2, Point2),
+        background: Rgb,
+    ) -> Self {
+        let size = max - min + Point2::new(1, 1);
+        let mut image = Image::new(size.x.max(0) as usize, size.y.max(0) as usize, background);
+        for (point, colour) in points {
+            let Some((row, col)) = (point - min).to_pos() else {
+                continue;
+            };
+            if col < image.width && row < image.height {
+                image.set(col, row, colour);
+            }
+        }
+        image
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Rgb {
+        self.pixels[y * self.width + x]
+    }
+
+    pub fn set(&mut self, x: usize, y: usize, colour: Rgb) {
+        self.pixels[y * self.width + x] = colour;
+    }
+
+    /// the image with every pixel drawn as a `factor` by `factor` square.
+    pub fn scaled(&self, factor: usize) -> Self {
+        let width = self.width * factor;
+        let pixels = (0..self.height * factor)
+            .flat_map(|y| (0..width).map(move |x| (x / factor, y / factor)))
+            .map(|(x, y)| self.get(x, y))
+            .collect();
+        Image {
+            width,
+            height: self.height * factor,
+            pixels,
+        }
+    }
+
+    /// the image in the top left corner of a larger canvas.
+    fn padded(&self, width: usize, height: usize, background: Rgb) -> Self {
+        let mut canvas = Image::new(width, height, background);
+        for y in 0..self.height {
+            for x in 0..self.width {
+                canvas.set(x, y, self.get(x, y));
+            }
+        }
+        canvas
+    }
+
+    #[cfg(feature = "export")]
+    fn bytes(&self) -> Vec<u8> {
+        self.pixels.concat()
+    }
+
+    pub fn save_png(&self, path: &Path) -> Result<(), ExportError> {
+        if self.pixels.is_empty() {
+            return Err(ExportError::Empty);
+        }
+        encode::png(self, path)
+    }
+}
+
+/// a frame of a recording.
+#[derive(Debug, Clone)]
+pub enum Drawing {
+    Image(Image),
+    /// coloured points. all point frames of a recording are drawn within the same bounds, so they line up.
+    Points(Vec<(Point2, Rgb)>),
+}
+
+impl From<Image> for Drawing {
+    fn from(image: Image) -> Self {
+        Drawing::Image(image)
+    }
+}
+
+/// a puzzle state that can be drawn as a frame of a recording.
+pub trait Export {
+    fn drawing(&self) -> Drawing;
+
+    /// the recording that `Runner::export` saves the states to, e.g. to change its scale or speed.
+    fn recording(name: &str) -> Recording
+    where
+        Self: Sized,
+    {
+        Recording::new(name)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Gif,
+    Png,
+}
+
+/// frames of an animation, collected while a solution runs.
+///
+/// # Example
+/// ```
+/// use advent_of_code::export::{Drawing, Recording, WHITE};
+/// use advent_of_code::helpers::Point2;
+///
+/// let mut recording = Recording::new("09-1").scale(4);
+/// for x in 0..5 {
+///     recording.capture(|| Drawing::Points(vec![(Point2::new(x, 0), WHITE)]));
+/// }
+/// recording.finish();
+/// ```
+#[derive(Debug)]
+pub struct Recording {
+    name: String,
+    format: Option<Format>,
+    frames: Vec<Drawing>,
+    captured: usize,
+    every: usize,
+    scale: usize,
+    fps: u16,
+    background: Rgb,
+}
+
+impl Recording {
+    /// a recording saved as `<name>.gif` or `<name>-<frame>.png`, if exporting is turned on.
+    pub fn new(name: &str) -> Self {
+        let format = match env::var(EXPORT_VAR).as_deref() {
+            Ok("gif") => Some(Format::Gif),
+            Ok("png") => Some(Format::Png),
+            _ => None,
+        };
+        Recording {
+            name: name.into(),
+            format,
+            frames: Vec::new(),
+            captured: 0,
+            every: 1,
+            scale: 4,
+            fps: 10,
+            background: BLACK,
+        }
+    }
+
+    /// only keeps every `n`th frame, to keep long animations small.
+    pub fn every(mut self, n: usize) -> Self {
+        self.every = n.max(1);
+        self
+    }
+
+    /// size of a cell or point in pixels, defaults to 4.
+    pub fn scale(mut self, scale: usize) -> Self {
+        self.scale = scale.max(1);
+        self
+    }
+
+    /// frames per second of the GIF, defaults to 10.
+    pub fn fps(mut self, fps: u16) -> Self {
+        self.fps = fps.clamp(1, 100);
+        self
+    }
+
+    /// colour of empty space, defaults to black.
+    pub fn background(mut self, background: Rgb) -> Self {
+        self.background = background;
+        self
+    }
+
+    /// whether frames are kept. `false` without the `export` feature, since they could not be saved.
+    pub fn is_active(&self) -> bool {
+        cfg!(feature = "export") && self.format.is_some()
+    }
+
+    /// adds the frame returned by `draw`. `draw` is only called if the frame is kept.
+    pub fn capture<D: Into<Drawing>>(&mut self, draw: impl FnOnce() -> D) {
+        if self.is_active() && self.captured % self.every == 0 {
+            self.frames.push(draw().into());
+        }
+        self.captured += 1;
+    }
+
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// all frames as images of the same size.
+    pub fn images(&self) -> Vec<Image> {
+        let points = self.frames.iter().flat_map(|frame| match frame {
+            Drawing::Points(points) => points.as_slice(),
+            Drawing::Image(_) => &[],
+        });
+        let bounds = points.fold(None, |bounds, &(p, _)| match bounds {
+            None => Some((p, p)),
+            Some((min, max)) => Some((
+                Point2::new(p.x.min(min.x), p.y.min(min.y)),
+                Point2::new(p.x.max(max.x), p.y.max(max.y)),
+            )),
+        });
+
+        let images: Vec<Image> = self
+            .frames
+            .iter()
+            .map(|frame| match frame {
+                Drawing::Image(image) => image.scaled(self.scale),
+                Drawing::Points(points) => {
+                    let bounds = bounds.unwrap_or_default();
+                    Image::from_points(points.iter().copied(), bounds, self.background)
+                        .scaled(self.scale)
+                }
+            })
+            .collect();
+
+        let width = images.iter().map(Image::width).max().unwrap_or_default();
+        let height = images.iter().map(Image::height).max().unwrap_or_default();
+        images
+            .iter()
+            .map(|image| image.padded(width, height, self.background))
+            .collect()
+    }
+
+    /// saves all frames as an animated GIF.
+    pub fn save_gif(&self, path: &Path) -> Result<(), ExportError> {
+        let images = self.images();
+        if images.first().is_none_or(|image| image.pixels.is_empty()) {
+            return Err(ExportError::Empty);
+        }
+        encode::gif(&images, self.fps, path)
+    }
+
+    /// saves every frame as a PNG, with the number of the frame appended to `path`. returns the paths.
+    pub fn save_pngs(&self, path: &Path) -> Result<Vec<PathBuf>, ExportError> {
+        let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+        self.images()
+            .iter()
+            .enumerate()
+            .map(|(idx, image)| {
+                let path = path.with_file_name(format!("{stem}-{:04}.png", idx + 1));
+                image.save_png(&path).map(|_| path)
+            })
+            .collect()
+    }
+
+    /// saves the recording to `target/visualisations` in the format set by `AOC_EXPORT`.
+    /// does nothing if exporting is turned off, errors are printed to stderr.
+    pub fn finish(self) {
+        let Some(format) = self.format.filter(|_| self.is_active()) else {
+            return;
+        };
+        let path = Path::new(EXPORT_DIR).join(&self.name);
+        let result = fs::create_dir_all(EXPORT_DIR)
+            .map_err(ExportError::from)
+            .and_then(|_| match format {
+                Format::Gif => {
+                    let path = path.with_extension("gif");
+                    self.save_gif(&path).map(|_| path.display().to_string())
+                }
+                Format::Png => self
+                    .save_pngs(&path)
+                    .map(|paths| format!("{} frames to {}-*.png", paths.len(), path.display())),
+            });
+
+        match result {
+            Ok(saved) => eprintln!("Saved {saved}."),
+            Err(e) => eprintln!("Could not save {}: {e}", self.name),
+        }
+    }
+}
+
+#[cfg(feature = "export")]
+mod encode {
+    use super::{ExportError, Image};
+    use std::{fs::File, io::BufWriter, path::Path};
+
+    pub fn png(image: &Image, path: &Path) -> Result<(), ExportError> {
+        let file = BufWriter::new(File::create(path)?);
+        let mut encoder = png::Encoder::new(file, image.width as u32, image.height as u32);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        encoder
+            .write_header()
+            .and_then(|mut writer| writer.write_image_data(&image.bytes()))
+            .map_err(ExportError::PngError)
+    }
+
+    pub fn gif(images: &[Image], fps: u16, path: &Path) -> Result<(), ExportError> {
+        let (width, height) = (images[0].width, images[0].height);
+        let too_large = || ExportError::TooLarge { width, height };
+        let gif_width = u16::try_from(width).map_err(|_| too_large())?;
+        let gif_height = u16::try_from(height).map_err(|_| too_large())?;
+
+        let file = BufWriter::new(File::create(path)?);
+        let mut encoder =
+            gif::Encoder::new(file, gif_width, gif_height, &[]).map_err(ExportError::GifError)?;
+        encoder
+            .set_repeat(gif::Repeat::Infinite)
+            .map_err(ExportError::GifError)?;
+
+        for image in images {
+            // speed 10 is the default of the gif crate, a good tradeoff between colours and speed.
+            let mut frame = gif::Frame::from_rgb_speed(gif_width, gif_height, &image.bytes(), 10);
+            frame.delay = 100 / fps;
+            encoder.write_frame(&frame).map_err(ExportError::GifError)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(not(feature = "export"))]
+mod encode {
+    use super::{ExportError, Image};
+    use std::path::Path;
+
+    pub fn png(_image: &Image, _path: &Path) -> Result<(), ExportError> {
+        Err(ExportError::Disabled)
+    }
+
+    pub fn gif(_images: &[Image], _fps: u16, _path: &Path) -> Result<(), ExportError> {
+        Err(ExportError::Disabled)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RED: Rgb = [255, 0, 0];
+
+    fn recording() -> Recording {
+        let mut recording = Recording::new("test").scale(2);
+        recording.format = Some(Format::Gif);
+        recording
+    }
+
+    #[test]
+    fn test_images() {
+        let grid: Grid<char> = "#.\n.#".parse().unwrap();
+        let image = Image::from_grid(&grid, |&c| if c == '#' { WHITE } else { BLACK });
+        assert_eq!((image.get(0, 0), image.get(1, 0)), (WHITE, BLACK));
+
+        let scaled = image.scaled(3);
+        assert_eq!((scaled.width(), scaled.height()), (6, 6));
+        assert_eq!((scaled.get(2, 2), scaled.get(3, 2)), (WHITE, BLACK));
+
+        let bounds = (Point2::new(-1, -1), Point2::new(1, 0));
+        let image = Image::from_points([(Point2::new(1, 0), RED)], bounds, BLACK);
+        assert_eq!((image.width(), image.height()), (3, 2));
+        assert_eq!(image.get(2, 1), RED);
+    }
+
+    #[test]
+    fn test_recording() {
+        let mut recording = recording();
+        recording.frames = [0, 2, 4]
+            .map(|x| Drawing::Points(vec![(Point2::new(x, -x), RED)]))
+            .into();
+        recording.frames.push(Image::new(1, 1, WHITE).into());
+
+        // points are drawn within the bounds of all points, images are padded to the largest frame.
+        let images = recording.images();
+        assert!(images.iter().all(|i| (i.width(), i.height()) == (10, 10)));
+        assert_eq!(images[0].get(0, 9), RED);
+        assert_eq!(images[1].get(4, 5), RED);
+        assert_eq!(images[3].get(1, 1), WHITE);
+    }
+
+    #[test]
+    fn test_capture() {
+        let mut recording = recording().every(2);
+        for x in 0..5 {
+            recording.capture(|| Drawing::Points(vec![(Point2::new(x, -x), RED)]));
+        }
+        // frames are only kept if they can be saved.
+        let expected = if cfg!(feature = "export") { 3 } else { 0 };
+        assert_eq!(recording.len(), expected);
+
+        let mut inactive = Recording::new("test");
+        inactive.format = None;
+        inactive.capture(|| -> Image { unreachable!() });
+        assert!(inactive.is_empty());
+    }
+
+    #[cfg(feature = "export")]
+    #[test]
+    fn test_save() {
+        let dir = env::temp_dir().join(format!("aoc-export-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let mut recording = recording();
+        recording.capture(|| Image::new(3, 2, RED));
+        recording.capture(|| Image::new(2, 3, WHITE));
+
+        recording.save_gif(&dir.join("test.gif")).unwrap();
+        let paths = recording.save_pngs(&dir.join("test")).unwrap();
+        assert_eq!(paths.len(), 2);
+        assert!(fs::read(&paths[1]).unwrap().starts_with(b"\x89PNG"));
+        assert!(fs::read(dir.join("test.gif"))
+            .unwrap()
+            .starts_with(b"GIF89a"));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -2,3 +2,21 @@
  * Use this file if you want to extract helpers from your solutions.
  * Example import from this file: `use advent_of_code::helpers::example_fn;`.
  */
+
+pub mod cycle;
+pub mod direction;
+pub mod grid;
+pub mod integers;
+pub mod interval;
+pub mod parse;
+pub mod point;
+pub mod search;
+pub mod simulation;
+
+pub use direction::Direction;
+pub use grid::Grid;
+pub use integers::{integers, integers_n, unsigned_integers, unsigned_integers_n};
+pub use interval::{Interval, RangeSet};
+pub use parse::{ParseError, Parser};
+pub use point::{Point2, Point3};
+pub use simulation::{Runner, Simulation};
@@ -0,0 +1,195 @@
+use std::{collections::HashMap, hash::Hash};
+
+/// a sequence of states that repeats from step `start` on, every `length` steps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    pub start: usize,
+    pub length: usize,
+}
+
+impl Cycle {
+    /// the earliest step with the same state as `step`.
+    pub fn reduce(&self, step: usize) -> usize {
+        if step < self.start {
+            step
+        } else {
+            self.start + (step - self.start) % self.length
+        }
+    }
+}
+
+/// finds the cycle of the states reached by repeatedly applying `step` to `initial`, in constant memory.
+/// uses Brent's algorithm, which applies `step` less often than Floyd's.
+///
+/// the sequence has to repeat eventually, or this never returns.
+pub fn brent<S: Clone + PartialEq>(initial: S, mut step: impl FnMut(&S) -> S) -> Cycle {
+    let mut power = 1;
+    let mut length = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = step(&initial);
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        hare = step(&hare);
+        length += 1;
+    }
+
+    // the hare starts one cycle ahead, they meet at the start of the cycle.
+    let mut tortoise = initial.clone();
+    let mut hare = initial;
+    for _ in 0..length {
+        hare = step(&hare);
+    }
+    let mut start = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    Cycle { start, length }
+}
+
+/// like `brent`, using Floyd's tortoise and hare algorithm.
+pub fn floyd<S: Clone + PartialEq>(initial: S, mut step: impl FnMut(&S) -> S) -> Cycle {
+    let mut tortoise = step(&initial);
+    let mut hare = step(&tortoise);
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        let halfway = step(&hare);
+        hare = step(&halfway);
+    }
+
+    let mut tortoise = initial;
+    let mut start = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    let mut length = 1;
+    let mut hare = step(&tortoise);
+    while tortoise != hare {
+        hare = step(&hare);
+        length += 1;
+    }
+
+    Cycle { start, length }
+}
+
+/// the states up to the first repetition and the cycle they form.
+#[derive(Debug, Clone)]
+pub struct History<S> {
+    pub cycle: Cycle,
+    /// all states from the initial one to the first repeated one, `cycle.start + cycle.length + 1` in total.
+    pub states: Vec<S>,
+}
+
+impl<S> History<S> {
+    /// the state after any number of steps.
+    pub fn state_at(&self, step: usize) -> &S {
+        &self.states[self.cycle.reduce(step)]
+    }
+
+    /// the value of `metric` after any number of steps, for metrics that grow by the same amount every cycle.
+    /// e.g. the height of a tower that is part of the state, while the key only looks at its top.
+    pub fn extrapolate(&self, step: usize, mut metric: impl FnMut(&S) -> i64) -> i64 {
+        if step < self.states.len() {
+            return metric(&self.states[step]);
+        }
+
+        let Cycle { start, length } = self.cycle;
+        let growth = metric(&self.states[start + length]) - metric(&self.states[start]);
+        let cycles = ((step - start) / length) as i64;
+        metric(&self.states[self.cycle.reduce(step)]) + cycles * growth
+    }
+}
+
+/// applies `step` to `initial` until a state repeats, comparing states by `key`.
+/// keeps all states, so the state or a metric can be looked up for any step afterwards.
+///
+/// the keys have to repeat eventually, or this never returns.
+///
+/// # Example
+/// ```
+/// use advent_of_code::helpers::cycle::find_cycle;
+///
+/// let history = find_cycle(0, |n| (n + 3) % 10, |&n| n);
+/// assert_eq!(history.cycle.length, 10);
+/// assert_eq!(*history.state_at(1_000_000_000_001), 3);
+/// ```
+pub fn find_cycle<S, K>(
+    initial: S,
+    mut step: impl FnMut(&S) -> S,
+    mut key: impl FnMut(&S) -> K,
+) -> History<S>
+where
+    K: Eq + Hash,
+{
+    let mut seen: HashMap<K, usize> = HashMap::new();
+    let mut states = vec![initial];
+
+    loop {
+        let current = states.len() - 1;
+        if let Some(start) = seen.insert(key(&states[current]), current) {
+            return History {
+                cycle: Cycle {
+                    start,
+                    length: current - start,
+                },
+                states,
+            };
+        }
+        states.push(step(&states[current]));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn next(x: &u64) -> u64 {
+        (x * x + 1) % 255
+    }
+
+    #[test]
+    fn test_algorithms_agree() {
+        for initial in [3, 10, 200] {
+            let history = find_cycle(initial, next, |&x| x);
+            assert_eq!(brent(initial, next), history.cycle);
+            assert_eq!(floyd(initial, next), history.cycle);
+        }
+    }
+
+    #[test]
+    fn test_extrapolate() {
+        // phases 0, 1, 2, 3, 4, 2, 3, 4, ... where the height grows by the phase every step.
+        let step = |&(phase, height): &(i64, i64)| {
+            let phase = if phase == 4 { 2 } else { phase + 1 };
+            (phase, height + phase)
+        };
+        let history = find_cycle((0, 0), step, |&(phase, _)| phase);
+        assert_eq!(
+            history.cycle,
+            Cycle {
+                start: 2,
+                length: 3
+            }
+        );
+
+        let mut state = (0, 0);
+        for n in 0..100 {
+            assert_eq!(history.state_at(n).0, state.0);
+            assert_eq!(history.extrapolate(n, |&(_, height)| height), state.1);
+            state = step(&state);
+        }
+        assert_eq!(
+            history.extrapolate(1_000_000_000_001, |&(_, height)| height),
+            3_000_000_000_000
+        );
+    }
+}
@@ -0,0 +1,134 @@
+use super::point::Point2;
+use std::{
+    error::Error,
+    fmt::{self, Display},
+    str::FromStr,
+};
+
+/// one of the four orthogonal directions. `Up` is towards the first row of a `Grid`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseDirectionError(String);
+
+impl Display for ParseDirectionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:?} is not a direction, expected one of U, R, D, L or N, E, S, W.",
+            self.0
+        )
+    }
+}
+
+impl Error for ParseDirectionError {}
+
+impl Direction {
+    /// all directions, clockwise starting at `Up`.
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    pub fn turn_right(self) -> Self {
+        Direction::ALL[(self as usize + 1) % 4]
+    }
+
+    pub fn turn_left(self) -> Self {
+        Direction::ALL[(self as usize + 3) % 4]
+    }
+
+    pub fn opposite(self) -> Self {
+        Direction::ALL[(self as usize + 2) % 4]
+    }
+
+    /// the `(row, col)` offset of one step, as used by `Grid`.
+    pub fn step(self) -> (isize, isize) {
+        match self {
+            Direction::Up => (-1, 0),
+            Direction::Right => (0, 1),
+            Direction::Down => (1, 0),
+            Direction::Left => (0, -1),
+        }
+    }
+}
+
+impl From<Direction> for (isize, isize) {
+    fn from(direction: Direction) -> Self {
+        direction.step()
+    }
+}
+
+impl From<Direction> for Point2 {
+    fn from(direction: Direction) -> Self {
+        let (row, col) = direction.step();
+        Point2::new(col as i64, row as i64)
+    }
+}
+
+impl TryFrom<char> for Direction {
+    type Error = ParseDirectionError;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c.to_ascii_uppercase() {
+            'U' | 'N' => Ok(Direction::Up),
+            'R' | 'E' => Ok(Direction::Right),
+            'D' | 'S' => Ok(Direction::Down),
+            'L' | 'W' => Ok(Direction::Left),
+            _ => Err(ParseDirectionError(c.to_string())),
+        }
+    }
+}
+
+/// parses `U`, `R`, `D`, `L` or `N`, `E`, `S`, `W`, in any case.
+impl FromStr for Direction {
+    type Err = ParseDirectionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Direction::try_from(c).map_err(|_| ParseDirectionError(s.into())),
+            _ => Err(ParseDirectionError(s.into())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_turning() {
+        assert_eq!(Direction::Up.turn_right(), Direction::Right);
+        assert_eq!(Direction::Up.turn_left(), Direction::Left);
+        assert_eq!(Direction::Left.opposite(), Direction::Right);
+        for direction in Direction::ALL {
+            assert_eq!(direction.turn_right().turn_left(), direction);
+            assert_eq!(direction.turn_right().turn_right(), direction.opposite());
+            assert_eq!(
+                Point2::from(direction) + Point2::from(direction.opposite()),
+                Point2::ORIGIN
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!("U".parse(), Ok(Direction::Up));
+        assert_eq!("w".parse(), Ok(Direction::Left));
+        assert_eq!(Direction::try_from('S'), Ok(Direction::Down));
+        assert_eq!(
+            "UP".parse::<Direction>(),
+            Err(ParseDirectionError("UP".into()))
+        );
+        assert!("".parse::<Direction>().is_err());
+    }
+}
@@ -0,0 +1,179 @@
+use std::{
+    any::type_name,
+    error::Error,
+    fmt::{self, Debug, Display},
+    marker::PhantomData,
+    str::FromStr,
+};
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct CountError {
+    pub expected: usize,
+    pub found: usize,
+}
+
+impl Display for CountError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "expected {} integers, found {}.",
+            self.expected, self.found
+        )
+    }
+}
+
+impl Error for CountError {}
+
+/// byte range of the next integer in `text`, if any.
+/// with `signed`, a `-` right before a digit is part of the integer. otherwise it is skipped like any other character.
+pub(crate) fn next_integer(text: &str, signed: bool) -> Option<(usize, usize)> {
+    let bytes = text.as_bytes();
+    let first_digit = bytes.iter().position(u8::is_ascii_digit)?;
+    let start = if signed && first_digit > 0 && bytes[first_digit - 1] == b'-' {
+        first_digit - 1
+    } else {
+        first_digit
+    };
+    let end = bytes[first_digit..]
+        .iter()
+        .position(|b| !b.is_ascii_digit())
+        .map_or(bytes.len(), |len| first_digit + len);
+    Some((start, end))
+}
+
+/// iterator over the integers in a text, created by [`integers`] and [`unsigned_integers`].
+#[derive(Debug, Clone)]
+pub struct Integers<'a, T> {
+    rest: &'a str,
+    signed: bool,
+    number: PhantomData<T>,
+}
+
+impl<T> Iterator for Integers<'_, T>
+where
+    T: FromStr,
+    T::Err: Debug,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        let (start, end) = next_integer(self.rest, self.signed)?;
+        let text = &self.rest[start..end];
+        self.rest = &self.rest[end..];
+
+        Some(
+            text.parse().unwrap_or_else(|e| {
+                panic!("could not parse {text:?} as {}: {e:?}", type_name::<T>())
+            }),
+        )
+    }
+}
+
+/// all integers in `text`, skipping any text between them. a `-` right before a digit is read as a sign.
+///
+/// # Panics
+/// when an integer does not fit into `T`.
+pub fn integers<T>(text: &str) -> Integers<'_, T> {
+    Integers {
+        rest: text,
+        signed: true,
+        number: PhantomData,
+    }
+}
+
+/// all integers in `text`, ignoring signs. `"2-4"` yields `2` and `4`.
+///
+/// # Panics
+/// when an integer does not fit into `T`.
+pub fn unsigned_integers<T>(text: &str) -> Integers<'_, T> {
+    Integers {
+        rest: text,
+        signed: false,
+        number: PhantomData,
+    }
+}
+
+/// exactly `N` integers, read like [`integers`].
+pub fn integers_n<T, const N: usize>(text: &str) -> Result<[T; N], CountError>
+where
+    T: FromStr,
+    T::Err: Debug,
+{
+    collect_n(integers(text))
+}
+
+/// exactly `N` integers, read like [`unsigned_integers`].
+pub fn unsigned_integers_n<T, const N: usize>(text: &str) -> Result<[T; N], CountError>
+where
+    T: FromStr,
+    T::Err: Debug,
+{
+    collect_n(unsigned_integers(text))
+}
+
+fn collect_n<T, const N: usize>(mut integers: Integers<'_, T>) -> Result<[T; N], CountError>
+where
+    T: FromStr,
+    T::Err: Debug,
+{
+    let mut found = 0;
+    let values: [Option<T>; N] = std::array::from_fn(|_| {
+        let value = integers.next();
+        found += usize::from(value.is_some());
+        value
+    });
+
+    // count the rest without parsing it, it might not fit into `T`.
+    while let Some((_, end)) = next_integer(integers.rest, integers.signed) {
+        integers.rest = &integers.rest[end..];
+        found += 1;
+    }
+    if found != N {
+        return Err(CountError { expected: N, found });
+    }
+
+    Ok(values.map(|value| value.unwrap()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_integers() {
+        let text = "move 3 from -1 to 2-4,x- 10";
+        assert_eq!(
+            integers::<i32>(text).collect::<Vec<_>>(),
+            [3, -1, 2, -4, 10]
+        );
+        assert_eq!(
+            unsigned_integers::<u32>(text).collect::<Vec<_>>(),
+            [3, 1, 2, 4, 10]
+        );
+        assert_eq!(integers::<u8>("no numbers").next(), None);
+        assert_eq!(integers::<u64>("12345678901").sum::<u64>(), 12345678901);
+    }
+
+    #[test]
+    fn test_integers_n() {
+        assert_eq!(unsigned_integers_n::<u32, 4>("2-4,6-8"), Ok([2, 4, 6, 8]));
+        assert_eq!(integers_n::<i8, 2>("x=-3, y=4"), Ok([-3, 4]));
+        assert_eq!(
+            integers_n::<u8, 2>("1 2 3 999"),
+            Err(CountError {
+                expected: 2,
+                found: 4
+            })
+        );
+        assert_eq!(
+            integers_n::<u8, 2>("1").unwrap_err().to_string(),
+            "expected 2 integers, found 1."
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "could not parse \"300\" as u8")]
+    fn test_overflow() {
+        integers::<u8>("1 300").for_each(drop);
+    }
+}
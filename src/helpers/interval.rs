@@ -0,0 +1,249 @@
+use std::ops::{Add, RangeInclusive, Sub};
+
+/// integer types that can bound an interval.
+pub trait Bound: Copy + Ord + Add<Output = Self> + Sub<Output = Self> {
+    const ZERO: Self;
+    const ONE: Self;
+}
+
+macro_rules! impl_bound {
+    ($($int:ty),+) => {
+        $(impl Bound for $int {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+        })+
+    };
+}
+
+impl_bound!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+/// the integers from `start` to `end`, both included.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Interval<T> {
+    pub start: T,
+    pub end: T,
+}
+
+impl<T: Bound> Interval<T> {
+    /// # Panics
+    /// if `start` is greater than `end`.
+    pub fn new(start: T, end: T) -> Self {
+        assert!(start <= end, "an interval cannot end before it starts");
+        Interval { start, end }
+    }
+
+    /// number of integers in the interval.
+    pub fn len(&self) -> T {
+        self.end - self.start + T::ONE
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        self.start <= value && value <= self.end
+    }
+
+    /// whether all of `other` lies within this interval.
+    pub fn encloses(&self, other: &Self) -> bool {
+        self.start <= other.start && other.end <= self.end
+    }
+
+    pub fn overlaps(&self, other: &Self) -> bool {
+        self.start <= other.end && other.start <= self.end
+    }
+
+    /// whether the intervals overlap or one ends right before the other starts.
+    pub fn touches(&self, other: &Self) -> bool {
+        self.overlaps(other)
+            || (self.end < other.start && other.start - self.end == T::ONE)
+            || (other.end < self.start && self.start - other.end == T::ONE)
+    }
+
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        self.overlaps(other)
+            .then(|| Interval::new(self.start.max(other.start), self.end.min(other.end)))
+    }
+
+    /// the interval covering both, `None` if there would be a gap between them.
+    pub fn union(&self, other: &Self) -> Option<Self> {
+        self.touches(other)
+            .then(|| Interval::new(self.start.min(other.start), self.end.max(other.end)))
+    }
+
+    /// the parts of this interval before and after `other`.
+    pub fn difference(&self, other: &Self) -> (Option<Self>, Option<Self>) {
+        let before = (self.start < other.start)
+            .then(|| Interval::new(self.start, self.end.min(other.start - T::ONE)));
+        let after = (self.end > other.end)
+            .then(|| Interval::new(self.start.max(other.end + T::ONE), self.end));
+        (before, after)
+    }
+}
+
+impl<T: Bound> From<RangeInclusive<T>> for Interval<T> {
+    fn from(range: RangeInclusive<T>) -> Self {
+        Interval::new(*range.start(), *range.end())
+    }
+}
+
+/// a set of integers, stored as sorted intervals that neither overlap nor touch.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RangeSet<T> {
+    intervals: Vec<Interval<T>>,
+}
+
+impl<T> Default for RangeSet<T> {
+    fn default() -> Self {
+        RangeSet {
+            intervals: Vec::new(),
+        }
+    }
+}
+
+impl<T: Bound> RangeSet<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// the intervals of the set, in ascending order.
+    pub fn intervals(&self) -> &[Interval<T>] {
+        &self.intervals
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// number of integers in the set.
+    pub fn len(&self) -> T {
+        self.intervals
+            .iter()
+            .fold(T::ZERO, |len, interval| len + interval.len())
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        let idx = self.intervals.partition_point(|i| i.end < value);
+        self.intervals.get(idx).is_some_and(|i| i.contains(value))
+    }
+
+    /// adds `interval`, merging it with the intervals it touches.
+    pub fn insert(&mut self, interval: impl Into<Interval<T>>) {
+        let mut merged = interval.into();
+        self.intervals.retain(|other| match merged.union(other) {
+            Some(union) => {
+                merged = union;
+                false
+            }
+            None => true,
+        });
+        let idx = self.intervals.partition_point(|i| i.start < merged.start);
+        self.intervals.insert(idx, merged);
+    }
+
+    /// removes all integers of `interval` from the set.
+    pub fn remove(&mut self, interval: impl Into<Interval<T>>) {
+        let removed = interval.into();
+        self.intervals = self
+            .intervals
+            .iter()
+            .flat_map(|i| {
+                let (before, after) = i.difference(&removed);
+                before.into_iter().chain(after)
+            })
+            .collect();
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        let mut union = self.clone();
+        for &interval in &other.intervals {
+            union.insert(interval);
+        }
+        union
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let intervals = self
+            .intervals
+            .iter()
+            .flat_map(|a| other.intervals.iter().filter_map(|b| a.intersection(b)))
+            .collect();
+        RangeSet { intervals }
+    }
+
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut difference = self.clone();
+        for &interval in &other.intervals {
+            difference.remove(interval);
+        }
+        difference
+    }
+
+    /// the intervals of `within` that are not in the set.
+    pub fn gaps(&self, within: impl Into<Interval<T>>) -> Self {
+        let mut gaps = RangeSet::new();
+        gaps.insert(within);
+        gaps.difference(self)
+    }
+}
+
+impl<T: Bound, I: Into<Interval<T>>> FromIterator<I> for RangeSet<T> {
+    fn from_iter<It: IntoIterator<Item = I>>(iter: It) -> Self {
+        let mut set = RangeSet::new();
+        for interval in iter {
+            set.insert(interval);
+        }
+        set
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_interval() {
+        let a = Interval::new(2, 8);
+        let b = Interval::from(3..=7);
+        let c = Interval::new(9, 12);
+
+        assert!(a.encloses(&b) && !b.encloses(&a));
+        assert!(a.overlaps(&b) && b.overlaps(&a));
+        assert!(!a.overlaps(&c) && a.touches(&c));
+        assert_eq!(a.len(), 7);
+        assert_eq!(a.intersection(&b), Some(b));
+        assert_eq!(a.intersection(&c), None);
+        assert_eq!(a.union(&c), Some(Interval::new(2, 12)));
+        assert_eq!(b.union(&c), None);
+        assert_eq!(
+            a.difference(&b),
+            (Some(Interval::new(2, 2)), Some(Interval::new(8, 8)))
+        );
+        assert_eq!(b.difference(&a), (None, None));
+        assert_eq!(a.difference(&c), (Some(a), None));
+    }
+
+    #[test]
+    fn test_range_set() {
+        let mut set: RangeSet<i64> = [-2..=2, 12..=12, 2..=14, 16..=20].into_iter().collect();
+        assert_eq!(
+            set.intervals(),
+            [Interval::new(-2, 14), Interval::new(16, 20)]
+        );
+        assert_eq!(set.len(), 22);
+        assert!(set.contains(16) && !set.contains(15));
+
+        set.remove(0..=17);
+        assert_eq!(
+            set.intervals(),
+            [Interval::new(-2, -1), Interval::new(18, 20)]
+        );
+        assert_eq!(
+            set.gaps(0..=25).intervals(),
+            [Interval::new(0, 17), Interval::new(21, 25)]
+        );
+
+        let other: RangeSet<i64> = [-1..=19].into_iter().collect();
+        assert_eq!(set.intersection(&other).len(), 3);
+        assert_eq!(set.union(&other).intervals(), [Interval::new(-2, 20)]);
+        assert_eq!(set.difference(&other).len(), 2);
+        assert!(RangeSet::<u8>::new().is_empty());
+    }
+}
@@ -0,0 +1,337 @@
+use super::integers::next_integer;
+use std::{
+    error::Error,
+    fmt::{self, Display},
+    str::FromStr,
+};
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseError {
+    /// 1-based line in the parsed input.
+    pub line: usize,
+    /// 1-based column in characters.
+    pub column: usize,
+    pub message: String,
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )
+    }
+}
+
+impl Error for ParseError {}
+
+/// reads a puzzle input from front to back.
+/// parsers for parts of the input, e.g. a line or a field, report errors at their position in the whole input.
+#[derive(Debug, Clone, Copy)]
+pub struct Parser<'a> {
+    source: &'a str,
+    rest: &'a str,
+}
+
+impl<'a> Parser<'a> {
+    pub fn new(input: &'a str) -> Self {
+        Parser {
+            source: input,
+            rest: input,
+        }
+    }
+
+    /// the text that has not been consumed yet.
+    pub fn rest(&self) -> &'a str {
+        self.rest
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rest.is_empty()
+    }
+
+    /// error at the current position.
+    pub fn error(&self, message: impl Into<String>) -> ParseError {
+        let offset = self.rest.as_ptr() as usize - self.source.as_ptr() as usize;
+        let before = &self.source[..offset];
+        let line_start = before.rfind('\n').map_or(0, |idx| idx + 1);
+
+        ParseError {
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            message: message.into(),
+        }
+    }
+
+    /// parser for `text`, which has to be a part of the rest of this parser.
+    fn part(&self, text: &'a str) -> Parser<'a> {
+        Parser {
+            source: self.source,
+            rest: text,
+        }
+    }
+
+    /// parser without the whitespace at either end.
+    pub fn trim(self) -> Self {
+        self.part(self.rest.trim())
+    }
+
+    pub fn skip_whitespace(&mut self) {
+        self.rest = self.rest.trim_start();
+    }
+
+    /// one parser per remaining line.
+    pub fn lines(&self) -> impl Iterator<Item = Parser<'a>> + '_ {
+        self.rest.lines().map(|line| self.part(line))
+    }
+
+    /// one parser per block of lines, with blocks separated by blank lines.
+    pub fn blocks(&self) -> impl Iterator<Item = Parser<'a>> + '_ {
+        self.rest
+            .split("\n\n")
+            .map(|block| self.part(block.trim_matches('\n')))
+            .filter(|block| !block.is_empty())
+    }
+
+    /// consumes the next line.
+    pub fn line(&mut self) -> Result<Parser<'a>, ParseError> {
+        if self.is_empty() {
+            return Err(self.error("expected another line"));
+        }
+        let (line, rest) = self.rest.split_once('\n').unwrap_or((self.rest, ""));
+        let line = self.part(line);
+        self.rest = rest;
+        Ok(line)
+    }
+
+    /// consumes `expected`, which has to come next.
+    pub fn literal(&mut self, expected: &str) -> Result<(), ParseError> {
+        match self.rest.strip_prefix(expected) {
+            Some(rest) => {
+                self.rest = rest;
+                Ok(())
+            }
+            None => Err(self.error(format!("expected {expected:?}"))),
+        }
+    }
+
+    /// consumes the text up to the next whitespace.
+    pub fn word(&mut self) -> Result<&'a str, ParseError> {
+        let end = self
+            .rest
+            .find(char::is_whitespace)
+            .unwrap_or(self.rest.len());
+        if end == 0 {
+            return Err(self.error("expected a word"));
+        }
+        let (word, rest) = self.rest.split_at(end);
+        self.rest = rest;
+        Ok(word)
+    }
+
+    /// consumes the text up to and including `delimiter` and returns a parser for the text before it.
+    pub fn until(&mut self, delimiter: &str) -> Result<Parser<'a>, ParseError> {
+        match self.rest.split_once(delimiter) {
+            Some((before, rest)) => {
+                let before = self.part(before);
+                self.rest = rest;
+                Ok(before)
+            }
+            None => Err(self.error(format!("expected {delimiter:?}"))),
+        }
+    }
+
+    /// consumes an integer with an optional sign.
+    pub fn integer<T>(&mut self) -> Result<T, ParseError>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        let sign = usize::from(self.rest.starts_with(['-', '+']));
+        let digits = self.rest[sign..]
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(self.rest.len() - sign);
+        if digits == 0 {
+            return Err(self.error("expected an integer"));
+        }
+
+        let (number, rest) = self.rest.split_at(sign + digits);
+        let value = self.part(number).parse()?;
+        self.rest = rest;
+        Ok(value)
+    }
+
+    /// all integers in the rest, read like `helpers::integers`, but reporting integers that do not fit into `T` as errors.
+    pub fn integers<T>(&self) -> Result<Vec<T>, ParseError>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        let mut parser = *self;
+        let mut integers = Vec::new();
+
+        while let Some((start, _)) = next_integer(parser.rest, true) {
+            parser.rest = &parser.rest[start..];
+            integers.push(parser.integer()?);
+        }
+        Ok(integers)
+    }
+
+    /// the whole rest, parsed with `FromStr`.
+    pub fn parse<T>(self) -> Result<T, ParseError>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        self.rest
+            .parse()
+            .map_err(|e| self.error(format!("could not parse {:?}: {e}", self.rest)))
+    }
+
+    /// the rest split at the first `separator`, e.g. `"key: value"`. both sides are trimmed.
+    pub fn key_value(&self, separator: &str) -> Result<(Parser<'a>, Parser<'a>), ParseError> {
+        let mut value = *self;
+        let key = value.until(separator)?;
+        Ok((key.trim(), value.trim()))
+    }
+
+    /// the rest split at every `separator`, with each item parsed by `item`.
+    pub fn separated<T>(
+        &self,
+        separator: &str,
+        mut item: impl FnMut(Parser<'a>) -> Result<T, ParseError>,
+    ) -> Result<Vec<T>, ParseError> {
+        self.rest
+            .split(separator)
+            .map(|part| item(self.part(part)))
+            .collect()
+    }
+
+    /// matches the rest against `pattern`, where each `{}` stands for a field of any text.
+    /// all text around the fields has to match literally.
+    ///
+    /// # Example
+    /// ```
+    /// use advent_of_code::helpers::parse::Parser;
+    ///
+    /// let [n, from, to] = Parser::new("move 1 from 2 to 3")
+    ///     .pattern("move {} from {} to {}")
+    ///     .unwrap();
+    /// assert_eq!(to.parse::<u32>(), Ok(3));
+    /// ```
+    ///
+    /// # Panics
+    /// if the pattern does not have `N` fields or two fields follow each other without text between them.
+    pub fn pattern<const N: usize>(&self, pattern: &str) -> Result<[Parser<'a>; N], ParseError> {
+        let mut literals = pattern.split("{}");
+        let mut parser = *self;
+        let mut fields = Vec::with_capacity(N);
+
+        parser.literal(literals.next().unwrap_or_default())?;
+        let literals: Vec<&str> = literals.collect();
+        assert_eq!(
+            literals.len(),
+            N,
+            "pattern {pattern:?} does not have {N} fields"
+        );
+
+        for (idx, literal) in literals.iter().enumerate() {
+            if literal.is_empty() && idx + 1 < N {
+                panic!("fields of pattern {pattern:?} need text between them");
+            }
+            let field = if literal.is_empty() {
+                let field = parser;
+                parser.rest = &parser.rest[parser.rest.len()..];
+                field
+            } else {
+                parser.until(literal)?
+            };
+            if field.is_empty() {
+                return Err(field.error("expected a value"));
+            }
+            fields.push(field);
+        }
+        parser.end()?;
+
+        Ok(fields.try_into().unwrap_or_else(|_| unreachable!()))
+    }
+
+    /// checks that everything was consumed.
+    pub fn end(&self) -> Result<(), ParseError> {
+        match self.rest.is_empty() {
+            true => Ok(()),
+            false => Err(self.error(format!("unexpected {:?}", self.rest))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_positions() {
+        let input = "ab\ncd\n\nef";
+        let parser = Parser::new(input);
+        let lines: Vec<_> = parser.lines().collect();
+        assert_eq!(lines[1].error("x").to_string(), "line 2, column 1: x");
+
+        let mut line = lines[1];
+        line.literal("c").unwrap();
+        assert_eq!(
+            line.literal("x"),
+            Err(ParseError {
+                line: 2,
+                column: 2,
+                message: "expected \"x\"".into()
+            })
+        );
+
+        let blocks: Vec<_> = parser.blocks().map(|block| block.rest()).collect();
+        assert_eq!(blocks, ["ab\ncd", "ef"]);
+        assert_eq!(parser.blocks().nth(1).unwrap().error("x").line, 4);
+    }
+
+    #[test]
+    fn test_integers() {
+        let mut parser = Parser::new("-12 +3 x");
+        assert_eq!(parser.integer::<i32>(), Ok(-12));
+        parser.skip_whitespace();
+        assert_eq!(parser.integer::<i32>(), Ok(3));
+        parser.skip_whitespace();
+        assert_eq!(parser.integer::<i32>().unwrap_err().column, 8);
+
+        let parser = Parser::new("move 3 from -1 to 2-4,x-");
+        assert_eq!(parser.integers::<i32>(), Ok(vec![3, -1, 2, -4]));
+        assert_eq!(Parser::new("a 300").integers::<u8>().unwrap_err().column, 3);
+    }
+
+    #[test]
+    fn test_fields() {
+        let parser = Parser::new("Starting items: 79, 98");
+        let (key, value) = parser.key_value(":").unwrap();
+        assert_eq!(key.rest(), "Starting items");
+        assert_eq!(value.separated(", ", Parser::parse), Ok(vec![79, 98]));
+
+        let mut parser = Parser::new("Valve AA has flow rate=0");
+        assert_eq!(parser.word(), Ok("Valve"));
+        assert_eq!(parser.until("=").unwrap().rest(), " AA has flow rate");
+        assert_eq!(parser.parse::<u32>(), Ok(0));
+    }
+
+    #[test]
+    fn test_pattern() {
+        let parser = Parser::new("x=1, y=-2");
+        let [x, y] = parser.pattern("x={}, y={}").unwrap();
+        assert_eq!((x.parse(), y.parse()), (Ok(1), Ok(-2)));
+
+        let error = parser.pattern::<2>("x={}; y={}").unwrap_err();
+        assert_eq!(
+            (error.column, error.message.as_str()),
+            (3, "expected \"; y=\"")
+        );
+        let error = parser.pattern::<1>("x={},").unwrap_err();
+        assert_eq!(error.column, 5);
+    }
+}
@@ -0,0 +1,250 @@
+use super::grid::Pos;
+use std::{
+    fmt::{self, Display},
+    ops::{Add, AddAssign, Div, Mul, Neg, Sub, SubAssign},
+};
+
+/// a point on a plane. `y` grows downwards, like the rows of a `Grid`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point2 {
+    pub x: i64,
+    pub y: i64,
+}
+
+/// a point in space.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point3 {
+    pub x: i64,
+    pub y: i64,
+    pub z: i64,
+}
+
+impl Point2 {
+    pub const ORIGIN: Point2 = Point2::new(0, 0);
+
+    pub const fn new(x: i64, y: i64) -> Self {
+        Point2 { x, y }
+    }
+
+    /// distance when moving along the axes only.
+    pub fn manhattan(self, other: Self) -> i64 {
+        (self.x - other.x).abs() + (self.y - other.y).abs()
+    }
+
+    /// distance when diagonal moves are allowed, as for a king in chess.
+    pub fn chebyshev(self, other: Self) -> i64 {
+        (self.x - other.x).abs().max((self.y - other.y).abs())
+    }
+
+    /// each coordinate clamped to `-1`, `0` or `1`.
+    pub fn signum(self) -> Self {
+        Point2::new(self.x.signum(), self.y.signum())
+    }
+
+    /// one step towards `other`, diagonal if both coordinates differ.
+    pub fn step_towards(self, other: Self) -> Self {
+        self + (other - self).signum()
+    }
+
+    /// the 4 orthogonal neighbours.
+    pub fn neighbours4(self) -> impl Iterator<Item = Self> {
+        [(0, -1), (1, 0), (0, 1), (-1, 0)]
+            .into_iter()
+            .map(move |(x, y)| self + Point2::new(x, y))
+    }
+
+    /// all 8 neighbours, including diagonal ones.
+    pub fn neighbours8(self) -> impl Iterator<Item = Self> {
+        (-1..=1)
+            .flat_map(|y| (-1..=1).map(move |x| Point2::new(x, y)))
+            .filter(|&step| step != Point2::ORIGIN)
+            .map(move |step| self + step)
+    }
+
+    /// the point of a `(row, col)` grid position.
+    pub fn from_pos((row, col): Pos) -> Self {
+        Point2::new(col as i64, row as i64)
+    }
+
+    /// the `(row, col)` grid position of the point, `None` if a coordinate is negative.
+    pub fn to_pos(self) -> Option<Pos> {
+        Some((self.y.try_into().ok()?, self.x.try_into().ok()?))
+    }
+}
+
+impl Point3 {
+    pub const ORIGIN: Point3 = Point3::new(0, 0, 0);
+
+    pub const fn new(x: i64, y: i64, z: i64) -> Self {
+        Point3 { x, y, z }
+    }
+
+    /// distance when moving along the axes only.
+    pub fn manhattan(self, other: Self) -> i64 {
+        (self.x - other.x).abs() + (self.y - other.y).abs() + (self.z - other.z).abs()
+    }
+
+    /// distance when diagonal moves are allowed.
+    pub fn chebyshev(self, other: Self) -> i64 {
+        (self.x - other.x)
+            .abs()
+            .max((self.y - other.y).abs())
+            .max((self.z - other.z).abs())
+    }
+
+    /// each coordinate clamped to `-1`, `0` or `1`.
+    pub fn signum(self) -> Self {
+        Point3::new(self.x.signum(), self.y.signum(), self.z.signum())
+    }
+
+    /// one step towards `other`, diagonal if more than one coordinate differs.
+    pub fn step_towards(self, other: Self) -> Self {
+        self + (other - self).signum()
+    }
+
+    /// the 6 neighbours that share a face.
+    pub fn neighbours6(self) -> impl Iterator<Item = Self> {
+        [
+            (-1, 0, 0),
+            (1, 0, 0),
+            (0, -1, 0),
+            (0, 1, 0),
+            (0, 0, -1),
+            (0, 0, 1),
+        ]
+        .into_iter()
+        .map(move |(x, y, z)| self + Point3::new(x, y, z))
+    }
+}
+
+/// implements the arithmetic operators component-wise, with `i64` as scalar.
+macro_rules! impl_ops {
+    ($point:ident { $($field:ident),+ }) => {
+        impl Add for $point {
+            type Output = Self;
+
+            fn add(self, other: Self) -> Self {
+                $point { $($field: self.$field + other.$field),+ }
+            }
+        }
+
+        impl Sub for $point {
+            type Output = Self;
+
+            fn sub(self, other: Self) -> Self {
+                $point { $($field: self.$field - other.$field),+ }
+            }
+        }
+
+        impl AddAssign for $point {
+            fn add_assign(&mut self, other: Self) {
+                *self = *self + other;
+            }
+        }
+
+        impl SubAssign for $point {
+            fn sub_assign(&mut self, other: Self) {
+                *self = *self - other;
+            }
+        }
+
+        impl Neg for $point {
+            type Output = Self;
+
+            fn neg(self) -> Self {
+                $point { $($field: -self.$field),+ }
+            }
+        }
+
+        impl Mul<i64> for $point {
+            type Output = Self;
+
+            fn mul(self, factor: i64) -> Self {
+                $point { $($field: self.$field * factor),+ }
+            }
+        }
+
+        impl Div<i64> for $point {
+            type Output = Self;
+
+            fn div(self, divisor: i64) -> Self {
+                $point { $($field: self.$field / divisor),+ }
+            }
+        }
+    };
+}
+
+impl_ops!(Point2 { x, y });
+impl_ops!(Point3 { x, y, z });
+
+impl From<(i64, i64)> for Point2 {
+    fn from((x, y): (i64, i64)) -> Self {
+        Point2::new(x, y)
+    }
+}
+
+impl From<(i64, i64, i64)> for Point3 {
+    fn from((x, y, z): (i64, i64, i64)) -> Self {
+        Point3::new(x, y, z)
+    }
+}
+
+impl Display for Point2 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{},{}", self.x, self.y)
+    }
+}
+
+impl Display for Point3 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{},{},{}", self.x, self.y, self.z)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_arithmetic() {
+        let a = Point2::new(1, 2);
+        let b = Point2::new(4, -2);
+        assert_eq!(a + b, Point2::new(5, 0));
+        assert_eq!(a - b, Point2::new(-3, 4));
+        assert_eq!(-a * 3, Point2::new(-3, -6));
+        assert_eq!(b / 2, Point2::new(2, -1));
+
+        let mut c = Point3::new(1, 2, 3);
+        c += Point3::new(1, 1, 1);
+        c -= Point3::new(0, 0, 5);
+        assert_eq!(c, Point3::new(2, 3, -1));
+    }
+
+    #[test]
+    fn test_distances() {
+        let a = Point2::new(1, 2);
+        let b = Point2::new(4, -2);
+        assert_eq!(a.manhattan(b), 7);
+        assert_eq!(a.chebyshev(b), 4);
+        assert_eq!(Point3::ORIGIN.manhattan(Point3::new(1, -2, 3)), 6);
+        assert_eq!(Point3::ORIGIN.chebyshev(Point3::new(1, -2, 3)), 3);
+    }
+
+    #[test]
+    fn test_stepping() {
+        let a = Point2::new(1, 2);
+        assert_eq!(a.step_towards(Point2::new(4, -2)), Point2::new(2, 1));
+        assert_eq!(a.step_towards(Point2::new(1, 5)), Point2::new(1, 3));
+        assert_eq!(a.step_towards(a), a);
+        assert_eq!(a.neighbours4().count(), 4);
+        assert!(a.neighbours8().all(|n| n != a && n.chebyshev(a) == 1));
+        assert_eq!(Point3::ORIGIN.neighbours6().count(), 6);
+    }
+
+    #[test]
+    fn test_grid_positions() {
+        assert_eq!(Point2::from_pos((3, 1)), Point2::new(1, 3));
+        assert_eq!(Point2::new(1, 3).to_pos(), Some((3, 1)));
+        assert_eq!(Point2::new(-1, 3).to_pos(), None);
+    }
+}
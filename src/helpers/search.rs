@@ -0,0 +1,327 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, HashSet, VecDeque},
+    hash::Hash,
+    ops::Add,
+};
+
+/// the nodes from start to goal, both included, and the cost of the steps between them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path<N, C> {
+    pub nodes: Vec<N>,
+    pub cost: C,
+}
+
+/// path from the start to `goal`, following the node each node was reached from.
+fn reconstruct<N: Clone + Eq + Hash>(parents: &HashMap<N, Option<N>>, goal: N) -> Vec<N> {
+    let mut nodes = vec![goal];
+    while let Some(Some(parent)) = parents.get(nodes.last().unwrap()) {
+        nodes.push(parent.clone());
+    }
+    nodes.reverse();
+    nodes
+}
+
+/// shortest path from `start` to the first node that satisfies `is_goal`, where every step costs 1.
+pub fn bfs<N, I>(
+    start: N,
+    mut neighbours: impl FnMut(&N) -> I,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<Path<N, usize>>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut parents = HashMap::from([(start.clone(), None)]);
+    let mut queue = VecDeque::from([start]);
+
+    while let Some(node) = queue.pop_front() {
+        if is_goal(&node) {
+            let nodes = reconstruct(&parents, node);
+            return Some(Path {
+                cost: nodes.len() - 1,
+                nodes,
+            });
+        }
+        for next in neighbours(&node) {
+            if !parents.contains_key(&next) {
+                parents.insert(next.clone(), Some(node.clone()));
+                queue.push_back(next);
+            }
+        }
+    }
+    None
+}
+
+/// number of steps from `start` to every node reachable from it.
+pub fn bfs_distances<N, I>(start: N, mut neighbours: impl FnMut(&N) -> I) -> HashMap<N, usize>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut distances = HashMap::from([(start.clone(), 0)]);
+    let mut queue = VecDeque::from([start]);
+
+    while let Some(node) = queue.pop_front() {
+        let distance = distances[&node] + 1;
+        for next in neighbours(&node) {
+            if !distances.contains_key(&next) {
+                distances.insert(next.clone(), distance);
+                queue.push_back(next);
+            }
+        }
+    }
+    distances
+}
+
+/// nodes with the cost of the cheapest path to them and the node that path came from.
+struct Costs<N, C> {
+    best: HashMap<N, (C, Option<N>)>,
+    goal: Option<N>,
+}
+
+/// best-first search, shared by `dijkstra`, `dijkstra_all` and `astar`.
+/// stops at the first goal if there is one. without a goal, all reachable nodes are visited.
+fn best_first<N, C, I>(
+    start: N,
+    mut neighbours: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Costs<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    // nodes are kept in a list so the heap does not need them to be ordered.
+    let mut nodes = vec![start.clone()];
+    let mut best = HashMap::from([(start.clone(), (C::default(), None))]);
+    let mut heap = BinaryHeap::from([Reverse((heuristic(&start), C::default(), 0))]);
+
+    while let Some(Reverse((_, cost, idx))) = heap.pop() {
+        let node = nodes[idx].clone();
+        if best[&node].0 < cost {
+            continue;
+        }
+        if is_goal(&node) {
+            return Costs {
+                best,
+                goal: Some(node),
+            };
+        }
+
+        for (next, step) in neighbours(&node) {
+            let next_cost = cost + step;
+            if best
+                .get(&next)
+                .is_some_and(|&(known, _)| known <= next_cost)
+            {
+                continue;
+            }
+            best.insert(next.clone(), (next_cost, Some(node.clone())));
+            heap.push(Reverse((
+                next_cost + heuristic(&next),
+                next_cost,
+                nodes.len(),
+            )));
+            nodes.push(next);
+        }
+    }
+    Costs { best, goal: None }
+}
+
+fn cheapest_path<N, C>(costs: Costs<N, C>) -> Option<Path<N, C>>
+where
+    N: Clone + Eq + Hash,
+    C: Copy,
+{
+    let goal = costs.goal?;
+    let cost = costs.best[&goal].0;
+    let parents: HashMap<N, Option<N>> = costs
+        .best
+        .into_iter()
+        .map(|(node, (_, parent))| (node, parent))
+        .collect();
+    Some(Path {
+        nodes: reconstruct(&parents, goal),
+        cost,
+    })
+}
+
+/// cheapest path from `start` to the first node that satisfies `is_goal`.
+/// `neighbours` yields the nodes reachable from a node with the cost of each step, costs must not be negative.
+pub fn dijkstra<N, C, I>(
+    start: N,
+    neighbours: impl FnMut(&N) -> I,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Option<Path<N, C>>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    cheapest_path(best_first(start, neighbours, |_| C::default(), is_goal))
+}
+
+/// cost of the cheapest path from `start` to every node reachable from it.
+pub fn dijkstra_all<N, C, I>(start: N, neighbours: impl FnMut(&N) -> I) -> HashMap<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    best_first(start, neighbours, |_| C::default(), |_| false)
+        .best
+        .into_iter()
+        .map(|(node, (cost, _))| (node, cost))
+        .collect()
+}
+
+/// like `dijkstra`, but guided by `heuristic`, an estimate of the cost from a node to the goal.
+/// the path is only guaranteed to be the cheapest if the heuristic never overestimates.
+pub fn astar<N, C, I>(
+    start: N,
+    neighbours: impl FnMut(&N) -> I,
+    heuristic: impl FnMut(&N) -> C,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Option<Path<N, C>>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    cheapest_path(best_first(start, neighbours, heuristic, is_goal))
+}
+
+/// all nodes reachable from `start`, including `start`.
+///
+/// # Example
+/// ```
+/// use advent_of_code::helpers::{search::flood_fill, Grid};
+///
+/// let grid: Grid<char> = "..#\n.##\n#..".parse().unwrap();
+/// let region = flood_fill((0, 0), |&pos| {
+///     grid.neighbours4(pos).filter(|&next| grid[next] == '.')
+/// });
+/// assert_eq!(region.len(), 3);
+/// ```
+pub fn flood_fill<N, I>(start: N, mut neighbours: impl FnMut(&N) -> I) -> HashSet<N>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut seen = HashSet::from([start.clone()]);
+    let mut stack = vec![start];
+
+    while let Some(node) = stack.pop() {
+        for next in neighbours(&node) {
+            if seen.insert(next.clone()) {
+                stack.push(next);
+            }
+        }
+    }
+    seen
+}
+
+/// groups `nodes` into sets of nodes that are reachable from each other.
+/// `neighbours` has to be symmetric: if `b` is a neighbour of `a`, `a` has to be a neighbour of `b`.
+pub fn components<N, I>(
+    nodes: impl IntoIterator<Item = N>,
+    mut neighbours: impl FnMut(&N) -> I,
+) -> Vec<HashSet<N>>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut seen = HashSet::new();
+    let mut components = Vec::new();
+
+    for node in nodes {
+        if seen.contains(&node) {
+            continue;
+        }
+        let component = flood_fill(node, &mut neighbours);
+        seen.extend(component.iter().cloned());
+        components.push(component);
+    }
+    components
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::Grid;
+
+    fn maze() -> Grid<char> {
+        "S.#.\n..#.\n#...\n.#.E".parse().unwrap()
+    }
+
+    #[test]
+    fn test_bfs() {
+        let grid = maze();
+        let open = |&pos: &(usize, usize)| grid.neighbours4(pos).filter(|&next| grid[next] != '#');
+
+        let path = bfs((0, 0), open, |&pos| grid[pos] == 'E').unwrap();
+        assert_eq!(path.cost, 6);
+        assert_eq!(path.nodes.first(), Some(&(0, 0)));
+        assert_eq!(path.nodes.last(), Some(&(3, 3)));
+        assert!(path
+            .nodes
+            .windows(2)
+            .all(|w| w[0].0.abs_diff(w[1].0) + w[0].1.abs_diff(w[1].1) == 1));
+
+        assert_eq!(bfs((0, 0), open, |&pos| pos == (3, 0)), None);
+        let distances = bfs_distances((0, 0), open);
+        assert_eq!(distances[&(0, 3)], 7);
+        assert!(!distances.contains_key(&(3, 0)));
+    }
+
+    #[test]
+    fn test_weighted() {
+        // a -> b -> d is cheaper than the direct a -> d.
+        let edges = |node: &char| match node {
+            'a' => vec![('b', 1), ('c', 5), ('d', 10)],
+            'b' => vec![('d', 2)],
+            'c' => vec![('d', 1)],
+            _ => vec![],
+        };
+
+        let path = dijkstra('a', edges, |&node| node == 'd').unwrap();
+        assert_eq!(
+            path,
+            Path {
+                nodes: vec!['a', 'b', 'd'],
+                cost: 3
+            }
+        );
+        let path = astar(
+            'a',
+            edges,
+            |&node| u32::from(node != 'd'),
+            |&node| node == 'd',
+        );
+        assert_eq!(path.map(|path| path.cost), Some(3));
+        assert_eq!(dijkstra('d', edges, |&node| node == 'a'), None);
+
+        let costs = dijkstra_all('a', edges);
+        assert_eq!(costs.len(), 4);
+        assert_eq!((costs[&'c'], costs[&'d']), (5, 3));
+    }
+
+    #[test]
+    fn test_regions() {
+        let grid = &maze();
+        let same = |&pos: &(usize, usize)| {
+            grid.neighbours4(pos)
+                .filter(move |&next| (grid[next] == '#') == (grid[pos] == '#'))
+        };
+
+        assert_eq!(flood_fill((3, 0), same).len(), 1);
+        assert_eq!(flood_fill((0, 0), same).len(), 11);
+
+        let regions = components(grid.positions(), same);
+        let mut sizes: Vec<_> = regions.iter().map(HashSet::len).collect();
+        sizes.sort();
+        assert_eq!(sizes, [1, 1, 1, 2, 11]);
+    }
+}
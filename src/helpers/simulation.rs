@@ -0,0 +1,311 @@
+use crate::export::{Drawing, Export, Recording};
+use crate::visualise::{self, Visualise};
+use std::{
+    fmt::Debug,
+    fs::File,
+    io::{BufWriter, Write},
+    path::{Path, PathBuf},
+};
+
+/// a puzzle that is solved by advancing a state step by step, e.g. rounds of a game or cycles of a CPU.
+pub trait Simulation {
+    /// advances the state by one step. returns `false` if the simulation is over and nothing changed.
+    fn step(&mut self) -> bool;
+}
+
+/// why a run ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stop {
+    /// `Simulation::step` returned `false`.
+    Finished,
+    /// the `until` predicate matched.
+    Until,
+    /// `max_steps` steps were done.
+    MaxSteps,
+}
+
+/// the outcome of `Runner::run`.
+#[derive(Debug)]
+pub struct Run<S> {
+    /// number of steps done.
+    pub steps: usize,
+    pub stop: Stop,
+    /// copies of the state with the number of steps done before it was taken.
+    pub snapshots: Vec<(usize, S)>,
+}
+
+/// a function that is called with the state and the number of steps done.
+type Hook<'a, S, T> = Box<dyn FnMut(&S, usize) -> T + 'a>;
+/// copies the state for a snapshot.
+type Snapshot<S> = fn(&S) -> S;
+/// renders the state as a line of the trace.
+type Describe<S> = fn(&S) -> String;
+/// draws the state as a frame of the recording.
+type Draw<S> = fn(&S) -> Drawing;
+
+/// runs a `Simulation` with optional limits, hooks, snapshots, tracing and recording.
+///
+/// # Example
+/// ```
+/// use advent_of_code::helpers::simulation::{Runner, Simulation, Stop};
+///
+/// struct Counter(u32);
+///
+/// impl Simulation for Counter {
+///     fn step(&mut self) -> bool {
+///         self.0 += 1;
+///         true
+///     }
+/// }
+///
+/// let run = Runner::new()
+///     .until(|counter: &Counter, _| counter.0 == 3)
+///     .run(&mut Counter(0));
+/// assert_eq!((run.steps, run.stop), (3, Stop::Until));
+/// ```
+pub struct Runner<'a, S> {
+    max_steps: Option<usize>,
+    until: Option<Hook<'a, S, bool>>,
+    observers: Vec<Hook<'a, S, ()>>,
+    snapshots: Option<(usize, Snapshot<S>)>,
+    trace: Option<(PathBuf, Describe<S>)>,
+    recording: Option<(Recording, Draw<S>)>,
+}
+
+impl<S> Default for Runner<'_, S> {
+    fn default() -> Self {
+        Runner {
+            max_steps: None,
+            until: None,
+            observers: Vec::new(),
+            snapshots: None,
+            trace: None,
+            recording: None,
+        }
+    }
+}
+
+impl<'a, S: Simulation> Runner<'a, S> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// stops after `steps` steps.
+    pub fn max_steps(mut self, steps: usize) -> Self {
+        self.max_steps = Some(steps);
+        self
+    }
+
+    /// stops as soon as `predicate` matches the state and the number of steps done, checked before every step.
+    pub fn until(mut self, predicate: impl FnMut(&S, usize) -> bool + 'a) -> Self {
+        self.until = Some(Box::new(predicate));
+        self
+    }
+
+    /// calls `observer` with the initial state and after every step, with the number of steps done.
+    pub fn observe(mut self, observer: impl FnMut(&S, usize) + 'a) -> Self {
+        self.observers.push(Box::new(observer));
+        self
+    }
+
+    /// shows the initial state and the state after every step as frames of the terminal animation.
+    /// does nothing unless the animation is active, see `advent_of_code::visualise`.
+    pub fn visualise(self) -> Self
+    where
+        S: Visualise,
+    {
+        if visualise::is_active() {
+            self.observe(|state, _| visualise::frame(state))
+        } else {
+            self
+        }
+    }
+
+    /// records the initial state and the state after every step, and saves them as `name` when the run ends.
+    /// does nothing unless exporting is turned on, see `advent_of_code::export`.
+    pub fn export(mut self, name: &str) -> Self
+    where
+        S: Export,
+    {
+        let recording = S::recording(name);
+        if recording.is_active() {
+            self.recording = Some((recording, S::drawing));
+        }
+        self
+    }
+
+    /// keeps a copy of the initial state and of the state after every `every` steps.
+    pub fn snapshot_every(mut self, every: usize) -> Self
+    where
+        S: Clone,
+    {
+        assert!(every > 0, "snapshots need to be at least one step apart");
+        self.snapshots = Some((every, S::clone));
+        self
+    }
+
+    /// writes the initial state and the state after every step to `path`, one line per step.
+    pub fn trace(mut self, path: impl AsRef<Path>) -> Self
+    where
+        S: Debug,
+    {
+        self.trace = Some((path.as_ref().to_path_buf(), |state| format!("{state:?}")));
+        self
+    }
+
+    /// runs `simulation` until it finishes or one of the limits is reached.
+    pub fn run(mut self, simulation: &mut S) -> Run<S> {
+        let mut trace = self
+            .trace
+            .take()
+            .and_then(|(path, describe)| match File::create(&path) {
+                Ok(file) => Some((BufWriter::new(file), describe)),
+                Err(e) => {
+                    eprintln!("Could not create trace file {}: {e}", path.display());
+                    None
+                }
+            });
+        let mut run = Run {
+            steps: 0,
+            stop: Stop::Finished,
+            snapshots: Vec::new(),
+        };
+
+        loop {
+            for observer in &mut self.observers {
+                observer(simulation, run.steps);
+            }
+            if let Some((recording, draw)) = &mut self.recording {
+                recording.capture(|| draw(simulation));
+            }
+            if let Some((every, copy)) = self.snapshots {
+                if run.steps % every == 0 {
+                    run.snapshots.push((run.steps, copy(simulation)));
+                }
+            }
+            if let Some((writer, describe)) = &mut trace {
+                if let Err(e) = writeln!(writer, "{}: {}", run.steps, describe(simulation)) {
+                    eprintln!("Could not write trace: {e}");
+                    trace = None;
+                }
+            }
+
+            if let Some(until) = &mut self.until {
+                if until(simulation, run.steps) {
+                    run.stop = Stop::Until;
+                    break;
+                }
+            }
+            if self.max_steps == Some(run.steps) {
+                run.stop = Stop::MaxSteps;
+                break;
+            }
+            if !simulation.step() {
+                run.stop = Stop::Finished;
+                break;
+            }
+            run.steps += 1;
+        }
+
+        if let Some((mut writer, _)) = trace {
+            if let Err(e) = writer.flush() {
+                eprintln!("Could not write trace: {e}");
+            }
+        }
+        if let Some((recording, _)) = self.recording {
+            recording.finish();
+        }
+        run
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    /// counts down from its value and then stops.
+    #[derive(Debug, Clone, PartialEq)]
+    struct Countdown(u32);
+
+    impl Simulation for Countdown {
+        fn step(&mut self) -> bool {
+            match self.0 {
+                0 => false,
+                _ => {
+                    self.0 -= 1;
+                    true
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_stops() {
+        let run = Runner::new().run(&mut Countdown(5));
+        assert_eq!((run.steps, run.stop), (5, Stop::Finished));
+
+        let mut countdown = Countdown(5);
+        let run = Runner::new().max_steps(2).run(&mut countdown);
+        assert_eq!((run.steps, run.stop), (2, Stop::MaxSteps));
+        assert_eq!(countdown, Countdown(3));
+
+        let run = Runner::new()
+            .max_steps(4)
+            .until(|countdown: &Countdown, _| countdown.0 < 3)
+            .run(&mut Countdown(5));
+        assert_eq!((run.steps, run.stop), (3, Stop::Until));
+    }
+
+    #[test]
+    fn test_observe_and_snapshots() {
+        let mut seen = Vec::new();
+        let run = Runner::new()
+            .observe(|countdown: &Countdown, steps| seen.push((steps, countdown.0)))
+            .snapshot_every(2)
+            .run(&mut Countdown(3));
+        assert_eq!(seen, [(0, 3), (1, 2), (2, 1), (3, 0)]);
+        assert_eq!(run.snapshots, [(0, Countdown(3)), (2, Countdown(1))]);
+    }
+
+    #[test]
+    fn test_export() {
+        use std::cell::Cell;
+
+        struct Drawn<'a>(Countdown, &'a Cell<usize>);
+
+        impl Simulation for Drawn<'_> {
+            fn step(&mut self) -> bool {
+                self.0.step()
+            }
+        }
+
+        impl Export for Drawn<'_> {
+            fn drawing(&self) -> Drawing {
+                self.1.set(self.1.get() + 1);
+                Drawing::Points(Vec::new())
+            }
+        }
+
+        // states are only drawn if the recording can be saved.
+        let drawn = Cell::new(0);
+        Runner::new()
+            .export("test-runner")
+            .run(&mut Drawn(Countdown(3), &drawn));
+        let expected = if Recording::new("test-runner").is_active() {
+            4
+        } else {
+            0
+        };
+        assert_eq!(drawn.get(), expected);
+    }
+
+    #[test]
+    fn test_trace() {
+        let path = std::env::temp_dir().join(format!("aoc-trace-{}.txt", std::process::id()));
+        Runner::new().trace(&path).run(&mut Countdown(2));
+        let trace = fs::read_to_string(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(trace, "0: Countdown(2)\n1: Countdown(1)\n2: Countdown(0)\n");
+    }
+}
@@ -0,0 +1,270 @@
+use crate::encryption::{self, EncryptionError};
+use sha2::{Digest, Sha256};
+use std::{
+    collections::BTreeMap,
+    error::Error,
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+/// checksums of the files in a folder, in the format of `sha256sum`.
+/// verify them by hand with `sha256sum -c checksums.txt` inside that folder.
+pub const CHECKSUMS_FILE: &str = "checksums.txt";
+
+/// formatting problems found in a file while loading it.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Issue {
+    ByteOrderMark,
+    CrlfLineEndings,
+    TrailingWhitespace,
+}
+
+impl Display for Issue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Issue::ByteOrderMark => write!(f, "starts with a byte order mark, it was removed."),
+            Issue::CrlfLineEndings => {
+                write!(f, "has CRLF line endings, they were converted to LF.")
+            }
+            Issue::TrailingWhitespace => {
+                write!(f, "ends with whitespace other than a single line break.")
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum IntegrityError {
+    IoError(io::Error),
+    EncryptionError(EncryptionError),
+    ChecksumMismatch {
+        checksums: PathBuf,
+        expected: String,
+        actual: String,
+    },
+}
+
+impl Display for IntegrityError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            IntegrityError::IoError(e) => write!(f, "{e}"),
+            IntegrityError::EncryptionError(e) => write!(f, "{e}"),
+            IntegrityError::ChecksumMismatch {
+                checksums,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "checksum {actual} does not match {expected} recorded in \"{}\". \
+                 download the file again, or remove its line there if you changed it on purpose.",
+                checksums.display()
+            ),
+        }
+    }
+}
+
+impl Error for IntegrityError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            IntegrityError::IoError(e) => Some(e),
+            IntegrityError::EncryptionError(e) => e.source(),
+            IntegrityError::ChecksumMismatch { .. } => None,
+        }
+    }
+}
+
+impl From<io::Error> for IntegrityError {
+    fn from(e: io::Error) -> Self {
+        IntegrityError::IoError(e)
+    }
+}
+
+/// removes a byte order mark and converts CRLF line endings to LF.
+/// trailing whitespace is only reported, some inputs are sensitive to whitespace.
+pub fn normalise(contents: &str) -> (String, Vec<Issue>) {
+    let mut issues = Vec::new();
+
+    let contents = match contents.strip_prefix('\u{feff}') {
+        Some(rest) => {
+            issues.push(Issue::ByteOrderMark);
+            rest
+        }
+        None => contents,
+    };
+
+    let contents = if contents.contains("\r\n") {
+        issues.push(Issue::CrlfLineEndings);
+        contents.replace("\r\n", "\n")
+    } else {
+        contents.to_string()
+    };
+
+    let content_end = contents.trim_end().len();
+    if content_end < contents.len() && &contents[content_end..] != "\n" {
+        issues.push(Issue::TrailingWhitespace);
+    }
+
+    (contents, issues)
+}
+
+pub fn checksum(bytes: &[u8]) -> String {
+    Sha256::digest(bytes)
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+fn checksums_path(path: &Path) -> PathBuf {
+    path.with_file_name(CHECKSUMS_FILE)
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+/// parses `<checksum>  <file name>` lines, keyed by file name.
+pub fn parse_checksums(contents: &str) -> BTreeMap<String, String> {
+    contents
+        .lines()
+        .filter_map(|line| {
+            let (checksum, name) = line.trim().split_once(char::is_whitespace)?;
+            // `sha256sum --binary` marks file names with a `*`.
+            let name = name.trim_start().trim_start_matches('*');
+            Some((name.to_string(), checksum.to_lowercase()))
+        })
+        .collect()
+}
+
+fn format_checksums(checksums: &BTreeMap<String, String>) -> String {
+    checksums
+        .iter()
+        .map(|(name, checksum)| format!("{checksum}  {name}\n"))
+        .collect()
+}
+
+fn read_checksums(path: &Path) -> io::Result<BTreeMap<String, String>> {
+    match fs::read_to_string(checksums_path(path)) {
+        Ok(contents) => Ok(parse_checksums(&contents)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(BTreeMap::new()),
+        Err(e) => Err(e),
+    }
+}
+
+/// stores the checksum of the file at `path` next to it, replacing an earlier one.
+pub fn record_checksum(path: &Path) -> io::Result<()> {
+    let mut checksums = read_checksums(path)?;
+    checksums.insert(file_name(path), checksum(&fs::read(path)?));
+    fs::write(checksums_path(path), format_checksums(&checksums))
+}
+
+/// compares `bytes` to the checksum recorded for `path`, if there is one.
+pub fn verify(path: &Path, bytes: &[u8]) -> Result<(), IntegrityError> {
+    let Some(expected) = read_checksums(path)?.remove(&file_name(path)) else {
+        return Ok(());
+    };
+
+    let actual = checksum(bytes);
+    if actual == expected {
+        Ok(())
+    } else {
+        Err(IntegrityError::ChecksumMismatch {
+            checksums: checksums_path(path),
+            expected,
+            actual,
+        })
+    }
+}
+
+/// contents of the file at `path`, decrypted from its encrypted copy if it is missing or empty.
+fn read_bytes(path: &Path) -> Result<Vec<u8>, IntegrityError> {
+    let plaintext = fs::read(path);
+    if matches!(&plaintext, Ok(bytes) if !bytes.is_empty()) {
+        return Ok(plaintext?);
+    }
+
+    match encryption::read_encrypted(path).map_err(IntegrityError::EncryptionError)? {
+        Some(decrypted) => Ok(decrypted),
+        None => Ok(plaintext?),
+    }
+}
+
+/// reads a file, verifies its checksum and normalises it.
+/// formatting issues are printed as warnings to stderr.
+pub fn read(path: &Path) -> Result<String, IntegrityError> {
+    let bytes = read_bytes(path)?;
+    verify(path, &bytes)?;
+
+    let contents =
+        String::from_utf8(bytes).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+    let (contents, issues) = normalise(&contents);
+    for issue in issues {
+        eprintln!("warning: \"{}\" {issue}", path.display());
+    }
+
+    Ok(contents)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalise() {
+        assert_eq!(normalise("a\nb\n"), ("a\nb\n".to_string(), vec![]));
+        assert_eq!(normalise("    [D]\n1"), ("    [D]\n1".to_string(), vec![]));
+        assert_eq!(
+            normalise("\u{feff}1\r\n\r\n2\r\n"),
+            (
+                "1\n\n2\n".to_string(),
+                vec![Issue::ByteOrderMark, Issue::CrlfLineEndings]
+            )
+        );
+        assert_eq!(
+            normalise("1\n2\n\n"),
+            ("1\n2\n\n".to_string(), vec![Issue::TrailingWhitespace])
+        );
+        assert_eq!(normalise("1 ").1, vec![Issue::TrailingWhitespace]);
+    }
+
+    #[test]
+    fn test_checksum() {
+        assert_eq!(
+            checksum(b"abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+
+    #[test]
+    fn test_parse_checksums() {
+        let checksums = parse_checksums("ABC  01.txt\ndef *02.txt\n\n");
+
+        assert_eq!(checksums["01.txt"], "abc");
+        assert_eq!(checksums["02.txt"], "def");
+        assert_eq!(format_checksums(&checksums), "abc  01.txt\ndef  02.txt\n");
+    }
+
+    #[test]
+    fn test_verify() {
+        let dir = std::env::temp_dir().join(format!("aoc-integrity-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("01.txt");
+
+        fs::write(&path, "1\r\n2\r\n").unwrap();
+        assert_eq!(read(&path).unwrap(), "1\n2\n");
+
+        record_checksum(&path).unwrap();
+        assert!(read(&path).is_ok());
+
+        fs::write(&path, "1\n3\n").unwrap();
+        assert!(matches!(
+            read(&path),
+            Err(IntegrityError::ChecksumMismatch { .. })
+        ));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -1,12 +1,24 @@
 /*
- * This file contains template code.
- * There is no need to edit this file unless you want to change template functionality.
- * Prefer `./helpers.rs` if you want to extract code from your solutions.
+ * This file, the modules it declares and the tools in `./bin/tools` contain template code.
+ * There is no need to edit them unless you want to change template functionality.
+ * `./helpers.rs` is the exception, prefer it if you want to extract code from your solutions.
  */
+use serde::Serialize;
 use std::env;
-use std::fs;
-
+use std::path::Path;
+
+pub mod answers;
+pub mod calendar;
+pub mod days;
+pub mod encryption;
+pub mod examples;
+pub mod export;
 pub mod helpers;
+pub mod integrity;
+pub mod puzzle;
+pub mod session;
+pub mod template;
+pub mod visualise;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
@@ -23,6 +35,7 @@ macro_rules! solve {
             let timer = Instant::now();
             let result = func(input);
             let elapsed = timer.elapsed();
+            advent_of_code::visualise::finish();
             match result {
                 Some(result) => {
                     println!(
@@ -46,37 +59,115 @@ pub fn read_file(folder: &str, day: u8) -> String {
 
     let filepath = cwd.join("src").join(folder).join(format!("{day:02}.txt"));
 
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+    read_checked(&filepath)
+}
+
+/// reads a file with normalised line endings, panicking if its checksum does not match.
+fn read_checked(filepath: &Path) -> String {
+    integrity::read(filepath)
+        .unwrap_or_else(|e| panic!("could not open input file \"{}\": {e}", filepath.display()))
+}
+
+/// reads the file for one part of a day, e.g. `src/examples/09-2.txt`.
+/// falls back to the file shared by both parts if there is none for `part`.
+pub fn read_file_part(folder: &str, day: u8, part: u8) -> String {
+    let cwd = env::current_dir().unwrap();
+
+    let filepath = cwd
+        .join("src")
+        .join(folder)
+        .join(format!("{day:02}-{part}.txt"));
+
+    if filepath.exists() {
+        read_checked(&filepath)
+    } else {
+        read_file(folder, day)
+    }
 }
 
 fn parse_time(val: &str, postfix: &str) -> f64 {
     val.split(postfix).next().unwrap().parse().unwrap()
 }
 
+/// milliseconds of a `(elapsed: ...)` timing printed by `solve!`.
+fn parse_elapsed(timing: &str) -> f64 {
+    // use `contains` istd. of `ends_with`: string may contain ANSI escape sequences.
+    // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
+    if timing.contains("ns)") {
+        parse_time(timing, "ns") / 1_000_000_f64
+    } else if timing.contains("µs)") {
+        parse_time(timing, "µs") / 1000_f64
+    } else if timing.contains("ms)") {
+        parse_time(timing, "ms")
+    } else if timing.contains("s)") {
+        parse_time(timing, "s") * 1000_f64
+    } else {
+        0_f64
+    }
+}
+
 pub fn parse_exec_time(output: &str) -> f64 {
     output.lines().fold(0_f64, |acc, l| {
         if !l.contains("elapsed:") {
             acc
         } else {
             let timing = l.split("(elapsed: ").last().unwrap();
-            // use `contains` istd. of `ends_with`: string may contain ANSI escape sequences.
-            // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
             if timing.contains("ns)") {
                 acc // range below rounding precision.
-            } else if timing.contains("µs)") {
-                acc + parse_time(timing, "µs") / 1000_f64
-            } else if timing.contains("ms)") {
-                acc + parse_time(timing, "ms")
-            } else if timing.contains("s)") {
-                acc + parse_time(timing, "s") * 1000_f64
             } else {
-                acc
+                acc + parse_elapsed(timing)
             }
         }
     })
 }
 
+/// answer of one part, as printed by `solve!`.
+#[derive(Debug, PartialEq, Serialize)]
+pub struct PartResult {
+    pub part: u8,
+    /// `None` if the part is not solved.
+    pub answer: Option<String>,
+    pub elapsed_ms: Option<f64>,
+}
+
+/// answers and timings of each part in the output of a solution.
+pub fn parse_results(output: &str) -> Vec<PartResult> {
+    let mut results: Vec<(u8, Vec<&str>)> = Vec::new();
+
+    for line in output.lines() {
+        let header = line
+            .split_once("Part ")
+            .filter(|_| line.starts_with('🎄'))
+            .and_then(|(_, rest)| rest.split(|c: char| !c.is_ascii_digit()).next())
+            .and_then(|part| part.parse().ok());
+
+        match (header, results.last_mut()) {
+            (Some(part), _) => results.push((part, Vec::new())),
+            (None, Some((_, lines))) => lines.push(line),
+            (None, None) => {}
+        }
+    }
+
+    results
+        .into_iter()
+        .map(|(part, lines)| {
+            let text = lines.join("\n");
+            let (answer, elapsed_ms) = match text.rsplit_once("(elapsed: ") {
+                Some((answer, timing)) => (
+                    Some(answer.trim_end_matches(ANSI_ITALIC).trim_end().to_string()),
+                    Some(parse_elapsed(timing)),
+                ),
+                None => (None, None),
+            };
+            PartResult {
+                part,
+                answer,
+                elapsed_ms,
+            }
+        })
+        .collect()
+}
+
 /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
 #[cfg(test)]
 macro_rules! assert_approx_eq {
@@ -94,6 +185,7 @@ macro_rules! assert_approx_eq {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::fs;
 
     #[test]
     fn test_parse_exec_time() {
@@ -122,32 +214,159 @@ mod tests {
             10400.50_f64
         );
     }
+
+    #[test]
+    fn test_parse_results() {
+        let output = format!(
+            "🎄 {ANSI_BOLD}Part 1{ANSI_RESET} 🎄\n24000 {ANSI_ITALIC}(elapsed: 1.50ms){ANSI_RESET}\n\
+             🎄 {ANSI_BOLD}Part 2{ANSI_RESET} 🎄\n##..\n..## {ANSI_ITALIC}(elapsed: 20.00µs){ANSI_RESET}\n\
+             🎄 {ANSI_BOLD}Part 3{ANSI_RESET} 🎄\nnot solved.\n"
+        );
+
+        let results = parse_results(&output);
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0].answer.as_deref(), Some("24000"));
+        assert_approx_eq!(results[0].elapsed_ms.unwrap(), 1.5_f64);
+        assert_eq!(results[1].answer.as_deref(), Some("##..\n..##"));
+        assert_approx_eq!(results[1].elapsed_ms.unwrap(), 0.02_f64);
+        assert_eq!(
+            results[2],
+            PartResult {
+                part: 3,
+                answer: None,
+                elapsed_ms: None
+            }
+        );
+    }
+
+    #[test]
+    fn test_days_are_registered() {
+        let answers = answers::load().expect("could not read answers file");
+
+        for &day in days::DAYS {
+            let module_path = format!("src/bin/{day:02}.rs");
+            assert!(
+                Path::new(&module_path).exists(),
+                "missing \"{module_path}\""
+            );
+            assert!(
+                answers.contains_key(&(day, 1)) && answers.contains_key(&(day, 2)),
+                "day {day} is missing from \"{}\"",
+                answers::ANSWERS_PATH
+            );
+        }
+
+        for entry in fs::read_dir("src/bin").unwrap() {
+            let file_name = entry.unwrap().file_name().into_string().unwrap();
+            if let Some(Ok(day)) = file_name.strip_suffix(".rs").map(str::parse::<u8>) {
+                assert!(
+                    days::DAYS.contains(&day),
+                    "day {day} is not registered in \"src/days.rs\""
+                );
+            }
+        }
+    }
 }
 
 pub mod aoc_cli {
+    use crate::{integrity, puzzle};
     use std::{
+        error::Error,
         fmt::Display,
         fs::create_dir_all,
+        io::{self, Read, Write},
+        path::Path,
         process::{Command, Output, Stdio},
     };
 
+    /// bytes of aoc-cli's stderr that are kept for `diagnose`.
+    const STDERR_TAIL: usize = 4096;
+
+    #[derive(Debug)]
     pub enum AocCliError {
-        CommandNotFound,
-        CommandNotCallable,
+        CommandNotFound(io::Error),
+        CommandNotCallable(io::Error),
         BadExitStatus(Output),
-        IoError,
+        IoError(io::Error),
+    }
+
+    impl AocCliError {
+        /// the end of the stderr that the failed aoc-cli call printed, if there was any.
+        pub fn stderr(&self) -> Option<String> {
+            match self {
+                AocCliError::BadExitStatus(output) => {
+                    let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+                    (!stderr.is_empty()).then_some(stderr)
+                }
+                _ => None,
+            }
+        }
+
+        /// guidance for failures we know how to fix.
+        pub fn hint(&self) -> Option<&'static str> {
+            match self {
+                AocCliError::CommandNotFound(_) => {
+                    Some("Try running \"cargo install aoc-cli --version 0.7.0\" to install it.")
+                }
+                AocCliError::BadExitStatus(_) => diagnose(&self.stderr()?),
+                _ => None,
+            }
+        }
+
+        /// whether calling aoc-cli again might succeed. failures that `diagnose` recognises,
+        /// like a rejected session cookie or a locked puzzle, fail the same way every time.
+        pub fn is_transient(&self) -> bool {
+            matches!(self, AocCliError::BadExitStatus(_)) && self.hint().is_none()
+        }
     }
 
     impl Display for AocCliError {
         fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
             match self {
-                AocCliError::CommandNotFound => write!(f, "aoc-cli is not present in environment."),
-                AocCliError::CommandNotCallable => write!(f, "aoc-cli could not be called."),
-                AocCliError::BadExitStatus(_) => {
-                    write!(f, "aoc-cli exited with a non-zero status.")
+                AocCliError::CommandNotFound(_) => {
+                    write!(f, "aoc-cli is not present in environment.")?
                 }
-                AocCliError::IoError => write!(f, "could not write output files to file system."),
+                AocCliError::CommandNotCallable(_) => write!(f, "aoc-cli could not be called.")?,
+                AocCliError::BadExitStatus(output) => match output.status.code() {
+                    Some(code) => write!(f, "aoc-cli exited with status {code}.")?,
+                    None => write!(f, "aoc-cli was terminated by a signal.")?,
+                },
+                AocCliError::IoError(_) => {
+                    write!(f, "could not write output files to file system.")?
+                }
+            }
+
+            if let Some(hint) = self.hint() {
+                write!(f, "\nhint: {hint}")?;
             }
+
+            Ok(())
+        }
+    }
+
+    impl Error for AocCliError {
+        fn source(&self) -> Option<&(dyn Error + 'static)> {
+            match self {
+                AocCliError::CommandNotFound(e)
+                | AocCliError::CommandNotCallable(e)
+                | AocCliError::IoError(e) => Some(e),
+                AocCliError::BadExitStatus(_) => None,
+            }
+        }
+    }
+
+    /// maps known aoc-cli failure messages to actionable guidance.
+    fn diagnose(stderr: &str) -> Option<&'static str> {
+        let stderr = stderr.to_lowercase();
+
+        if stderr.contains("session cookie") || stderr.contains(".adventofcode.session") {
+            Some("Could not load your session cookie. Paste the `session` cookie from adventofcode.com into \"~/.adventofcode.session\".")
+        } else if stderr.contains("400 bad request") || stderr.contains("unauthorized") {
+            Some("Your session cookie was rejected. It might have expired, refresh \"~/.adventofcode.session\".")
+        } else if stderr.contains("404") || stderr.contains("locked") {
+            Some("The puzzle is not available yet. Puzzles unlock at midnight EST (UTC-5) on their day.")
+        } else {
+            None
         }
     }
 
@@ -155,21 +374,27 @@ pub mod aoc_cli {
         Command::new("aoc")
             .arg("-V")
             .output()
-            .map_err(|_| AocCliError::CommandNotFound)?;
+            .map_err(AocCliError::CommandNotFound)?;
         Ok(())
     }
 
     pub fn read(day: u8, year: Option<u16>) -> Result<Output, AocCliError> {
-        // TODO: output local puzzle if present.
         let args = build_args("read", &[], day, year);
-        call_aoc_cli(&args)
+        let output = call_aoc_cli(&args)?;
+
+        if output.status.success() {
+            Ok(output)
+        } else {
+            Err(AocCliError::BadExitStatus(output))
+        }
     }
 
     pub fn download(day: u8, year: Option<u16>) -> Result<Output, AocCliError> {
         let input_path = get_input_path(day);
 
         let puzzle_path = get_puzzle_path(day);
-        create_dir_all("src/puzzles").map_err(|_| AocCliError::IoError)?;
+        let previous = puzzle::read_local(day).map_err(AocCliError::IoError)?;
+        create_dir_all("src/puzzles").map_err(AocCliError::IoError)?;
 
         let args = build_args(
             "download",
@@ -187,6 +412,8 @@ pub mod aoc_cli {
         let output = call_aoc_cli(&args)?;
 
         if output.status.success() {
+            snapshot_if_changed(day, previous)?;
+            integrity::record_checksum(Path::new(&input_path)).map_err(AocCliError::IoError)?;
             println!("---");
             println!("🎄 Successfully wrote input to \"{}\".", &input_path);
             println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
@@ -196,19 +423,85 @@ pub mod aoc_cli {
         }
     }
 
+    /// downloads only the puzzle description, leaving the input untouched.
+    /// returns whether the description changed, e.g. because part two was unlocked.
+    pub fn refresh_puzzle(day: u8, year: Option<u16>) -> Result<bool, AocCliError> {
+        let puzzle_path = get_puzzle_path(day);
+        let previous = puzzle::read_local(day).map_err(AocCliError::IoError)?;
+        create_dir_all("src/puzzles").map_err(AocCliError::IoError)?;
+
+        let args = build_args(
+            "download",
+            &[
+                "--overwrite".into(),
+                "--puzzle-only".into(),
+                "--puzzle-file".into(),
+                puzzle_path.to_string(),
+            ],
+            day,
+            year,
+        );
+
+        let output = call_aoc_cli(&args)?;
+        if !output.status.success() {
+            return Err(AocCliError::BadExitStatus(output));
+        }
+
+        snapshot_if_changed(day, previous)
+    }
+
+    /// keeps the previous puzzle description around if a download replaced it.
+    /// downloads that leave the description as it was keep the existing snapshot.
+    fn snapshot_if_changed(day: u8, previous: Option<String>) -> Result<bool, AocCliError> {
+        let current = puzzle::read_local(day).map_err(AocCliError::IoError)?;
+        match previous {
+            Some(previous) if Some(&previous) != current.as_ref() => {
+                puzzle::snapshot(day, &previous).map_err(AocCliError::IoError)?;
+                Ok(true)
+            }
+            Some(_) => Ok(false),
+            None => Ok(current.is_some()),
+        }
+    }
+
+    pub fn submit(
+        day: u8,
+        year: Option<u16>,
+        part: u8,
+        answer: &str,
+    ) -> Result<Output, AocCliError> {
+        let mut args = build_args("submit", &[], day, year);
+        args.push(part.to_string());
+        args.push(answer.into());
+
+        let output = call_aoc_cli(&args)?;
+
+        if output.status.success() {
+            Ok(output)
+        } else {
+            Err(AocCliError::BadExitStatus(output))
+        }
+    }
+
     fn get_input_path(day: u8) -> String {
         let day_padded = format!("{day:02}");
         format!("src/inputs/{day_padded}.txt")
     }
 
     fn get_puzzle_path(day: u8) -> String {
-        let day_padded = format!("{day:02}");
-        format!("src/puzzles/{day_padded}.md")
+        puzzle::puzzle_path(day)
     }
 
     fn build_args(command: &str, args: &[String], day: u8, year: Option<u16>) -> Vec<String> {
         let mut cmd_args = args.to_vec();
 
+        if let Some(profile) = crate::session::resolve_profile(year) {
+            if let Ok(path) = crate::session::session_path(Some(&profile)) {
+                cmd_args.push("--session-file".into());
+                cmd_args.push(path.to_string_lossy().into());
+            }
+        }
+
         if let Some(year) = year {
             cmd_args.push("--year".into());
             cmd_args.push(year.to_string());
@@ -224,11 +517,68 @@ pub mod aoc_cli {
             println!("Calling >aoc with: {}", args.join(" "));
         }
 
-        Command::new("aoc")
+        let mut child = Command::new("aoc")
             .args(args)
             .stdout(Stdio::inherit())
-            .stderr(Stdio::inherit())
-            .output()
-            .map_err(|_| AocCliError::CommandNotCallable)
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(AocCliError::CommandNotCallable)?;
+
+        // stderr is passed through as it arrives, only its tail is kept to diagnose failures.
+        let mut stderr = child.stderr.take().expect("stderr is piped");
+        let mut tail = Vec::new();
+        let mut buf = [0; 1024];
+        loop {
+            let n = stderr.read(&mut buf).map_err(AocCliError::IoError)?;
+            if n == 0 {
+                break;
+            }
+            io::stderr()
+                .write_all(&buf[..n])
+                .map_err(AocCliError::IoError)?;
+            tail.extend_from_slice(&buf[..n]);
+            tail.drain(..tail.len().saturating_sub(STDERR_TAIL));
+        }
+
+        let status = child.wait().map_err(AocCliError::CommandNotCallable)?;
+        Ok(Output {
+            status,
+            stdout: Vec::new(),
+            stderr: tail,
+        })
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn test_diagnose() {
+            assert!(diagnose("error: Failed to read session cookie from \"/home/me/.adventofcode.session\": No such file or directory")
+                .unwrap()
+                .contains("~/.adventofcode.session"));
+            assert!(diagnose("error: HTTP status client error (404 Not Found) for url (https://adventofcode.com/2022/day/25)")
+                .unwrap()
+                .contains("midnight EST"));
+            assert_eq!(diagnose("error: something unexpected"), None);
+        }
+
+        #[cfg(unix)]
+        #[test]
+        fn test_is_transient() {
+            use std::os::unix::process::ExitStatusExt;
+            let failed = |stderr: &str| {
+                AocCliError::BadExitStatus(Output {
+                    status: std::process::ExitStatus::from_raw(1 << 8),
+                    stdout: Vec::new(),
+                    stderr: stderr.into(),
+                })
+            };
+
+            assert!(failed("error: error sending request for url").is_transient());
+            assert!(!failed("error: HTTP status client error (404 Not Found)").is_transient());
+            assert!(!failed("error: HTTP status client error (400 Bad Request)").is_transient());
+            assert!(!AocCliError::CommandNotFound(io::ErrorKind::NotFound.into()).is_transient());
+        }
     }
 }
@@ -0,0 +1,174 @@
+use crate::{ANSI_BOLD, ANSI_RESET};
+use std::{fs, io, path::Path};
+
+/// earlier versions of puzzle descriptions are kept here to highlight what changed.
+const SNAPSHOT_DIR: &str = ".cache/puzzles";
+
+pub fn puzzle_path(day: u8) -> String {
+    format!("src/puzzles/{day:02}.md")
+}
+
+pub fn snapshot_path(day: u8) -> String {
+    format!("{SNAPSHOT_DIR}/{day:02}.md")
+}
+
+/// stores the version of a puzzle description to diff against.
+/// this is the description before the last download that changed it. it is only replaced
+/// when the description changes again, so highlights stay until e.g. part two is unlocked.
+pub fn snapshot(day: u8, contents: &str) -> io::Result<()> {
+    fs::create_dir_all(SNAPSHOT_DIR)?;
+    fs::write(snapshot_path(day), contents)
+}
+
+fn read_if_present(path: &str) -> io::Result<Option<String>> {
+    if Path::new(path).exists() {
+        fs::read_to_string(path).map(Some)
+    } else {
+        Ok(None)
+    }
+}
+
+pub fn read_local(day: u8) -> io::Result<Option<String>> {
+    read_if_present(&puzzle_path(day))
+}
+
+/// puzzle name from a description's `--- Day 1: Calorie Counting ---` heading.
+pub fn parse_title(description: &str) -> Option<String> {
+    let heading = description.lines().find(|l| l.contains("--- Day "))?;
+    let (_, title) = heading.split_once(": ")?;
+    Some(title.trim_end().trim_end_matches("---").trim().to_string())
+}
+
+/// example input from a puzzle description.
+/// this is the first code block after the text mentions an example, or the first code block.
+pub fn extract_example(description: &str) -> Option<String> {
+    let mut blocks: Vec<(usize, Vec<&str>)> = Vec::new();
+    let mut current: Option<(usize, Vec<&str>)> = None;
+
+    for (idx, line) in description.lines().enumerate() {
+        if line.trim() == "```" {
+            match current.take() {
+                Some(block) => blocks.push(block),
+                None => current = Some((idx, Vec::new())),
+            }
+        } else if let Some((_, lines)) = current.as_mut() {
+            lines.push(line);
+        }
+    }
+
+    let mentions_example = description
+        .lines()
+        .position(|line| line.to_lowercase().contains("for example"));
+
+    let (_, lines) = mentions_example
+        .and_then(|mention| blocks.iter().find(|(start, _)| *start > mention))
+        .or(blocks.first())?;
+
+    let example = lines.join("\n");
+    Some(example.trim_end_matches('\n').to_string())
+}
+
+/// local puzzle description, with lines added since the last snapshot highlighted.
+pub fn render(day: u8) -> io::Result<Option<String>> {
+    let Some(current) = read_local(day)? else {
+        return Ok(None);
+    };
+
+    let rendered = match read_if_present(&snapshot_path(day))? {
+        Some(previous) => diff_lines(&previous, &current)
+            .into_iter()
+            .map(|(line, is_new)| {
+                if is_new && !line.trim().is_empty() {
+                    format!("{ANSI_BOLD}+ {line}{ANSI_RESET}")
+                } else {
+                    format!("  {line}")
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("\n"),
+        None => current,
+    };
+
+    Ok(Some(rendered))
+}
+
+/// lines of `new`, each flagged with whether it is absent from `old`.
+/// uses the longest common subsequence of lines, puzzle descriptions are short.
+pub fn diff_lines<'a>(old: &str, new: &'a str) -> Vec<(&'a str, bool)> {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+
+    // lcs[i][j] is the length of the lcs of old[i..] and new[j..].
+    let mut lcs = vec![vec![0_usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut diff = Vec::with_capacity(new.len());
+    let (mut i, mut j) = (0, 0);
+    while j < new.len() {
+        if i < old.len() && old[i] == new[j] {
+            diff.push((new[j], false));
+            i += 1;
+            j += 1;
+        } else if i < old.len() && lcs[i + 1][j] >= lcs[i][j + 1] {
+            i += 1;
+        } else {
+            diff.push((new[j], true));
+            j += 1;
+        }
+    }
+
+    diff
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_title() {
+        assert_eq!(
+            parse_title("\\--- Day 1: Calorie Counting ---\n----------\n"),
+            Some("Calorie Counting".to_string())
+        );
+        assert_eq!(parse_title("no heading"), None);
+    }
+
+    #[test]
+    fn test_extract_example() {
+        for day in [5, 7] {
+            let description = read_local(day).unwrap().unwrap();
+            let example = fs::read_to_string(format!("src/examples/{day:02}.txt")).unwrap();
+            assert_eq!(
+                extract_example(&description).as_deref(),
+                Some(example.trim_end_matches('\n'))
+            );
+        }
+
+        assert_eq!(extract_example("no code blocks"), None);
+    }
+
+    #[test]
+    fn test_diff_lines() {
+        let old = "## Part One\nfoo\nYour puzzle answer was ...\n";
+        let new = "## Part One\nfoo\nYour puzzle answer was `42`.\n## Part Two\nbar\n";
+
+        assert_eq!(
+            diff_lines(old, new),
+            vec![
+                ("## Part One", false),
+                ("foo", false),
+                ("Your puzzle answer was `42`.", true),
+                ("## Part Two", true),
+                ("bar", true),
+            ]
+        );
+    }
+}
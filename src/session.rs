@@ -0,0 +1,348 @@
+use std::{
+    env,
+    error::Error,
+    fmt::Display,
+    fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+    process::{Command, Output, Stdio},
+    time::{Duration, SystemTime},
+};
+
+/// session cookies are valid for roughly a month after logging in.
+pub const SESSION_LIFETIME: Duration = Duration::from_secs(30 * 24 * 60 * 60);
+/// warn about an expiring session once less than this is left.
+pub const EXPIRY_WARNING: Duration = Duration::from_secs(5 * 24 * 60 * 60);
+
+/// selects a named profile instead of the default session file.
+pub const PROFILE_VAR: &str = "AOC_PROFILE";
+/// session token that takes precedence over any session file, also used by aoc-cli.
+pub const SESSION_VAR: &str = "ADVENT_OF_CODE_SESSION";
+
+#[derive(Debug)]
+pub enum SessionError {
+    HomeNotFound,
+    NotFound(PathBuf),
+    Empty,
+    InvalidProfile(String),
+    Io(io::Error),
+    CurlNotCallable(io::Error),
+    /// adventofcode.com could not be reached or answered unexpectedly, the session might still be valid.
+    Network(String),
+}
+
+impl Display for SessionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SessionError::HomeNotFound => write!(f, "could not determine home directory."),
+            SessionError::NotFound(path) => write!(
+                f,
+                "no session cookie found at \"{}\". Run `cargo session set <token>` to add one.",
+                path.display()
+            ),
+            SessionError::Empty => write!(f, "session cookie is empty."),
+            SessionError::InvalidProfile(profile) => write!(
+                f,
+                "invalid profile name \"{profile}\". Use only letters, digits, `-` and `_`."
+            ),
+            SessionError::Io(_) => write!(f, "could not access session file."),
+            SessionError::CurlNotCallable(_) => write!(f, "curl could not be called."),
+            SessionError::Network(e) => write!(f, "could not check session: {e}"),
+        }
+    }
+}
+
+impl Error for SessionError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            SessionError::Io(e) | SessionError::CurlNotCallable(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+pub struct Session {
+    pub token: String,
+    /// file the token was read from, `None` if it came from the environment.
+    pub path: Option<PathBuf>,
+    pub saved_at: Option<SystemTime>,
+}
+
+impl Session {
+    /// token with everything but its first and last four characters hidden.
+    pub fn redacted(&self) -> String {
+        redact(&self.token)
+    }
+
+    /// estimated time until the cookie expires, based on when it was saved.
+    pub fn remaining(&self) -> Option<Duration> {
+        let age = self.saved_at?.elapsed().unwrap_or_default();
+        Some(SESSION_LIFETIME.saturating_sub(age))
+    }
+
+    pub fn is_expiring(&self) -> bool {
+        self.remaining()
+            .is_some_and(|remaining| remaining < EXPIRY_WARNING)
+    }
+}
+
+fn redact(token: &str) -> String {
+    let chars: Vec<char> = token.chars().collect();
+    if chars.len() <= 8 {
+        return "*".repeat(chars.len());
+    }
+
+    let head: String = chars[..4].iter().collect();
+    let tail: String = chars[chars.len() - 4..].iter().collect();
+    format!("{head}{}{tail}", "*".repeat(chars.len() - 8))
+}
+
+pub(crate) fn home_dir() -> Result<PathBuf, SessionError> {
+    env::var_os("HOME")
+        .or_else(|| env::var_os("USERPROFILE"))
+        .map(PathBuf::from)
+        .ok_or(SessionError::HomeNotFound)
+}
+
+/// profile names become part of a file name, so they may not contain separators like `/` or `.`.
+fn is_valid_profile(profile: &str) -> bool {
+    !profile.is_empty()
+        && profile
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b == b'-' || b == b'_')
+}
+
+fn file_name(profile: Option<&str>) -> Result<String, SessionError> {
+    match profile {
+        Some(profile) if !is_valid_profile(profile) => {
+            Err(SessionError::InvalidProfile(profile.into()))
+        }
+        Some(profile) => Ok(format!(".adventofcode.{profile}.session")),
+        None => Ok(".adventofcode.session".into()),
+    }
+}
+
+pub fn session_path(profile: Option<&str>) -> Result<PathBuf, SessionError> {
+    Ok(home_dir()?.join(file_name(profile)?))
+}
+
+/// profile to use when none was passed explicitly.
+/// `$AOC_PROFILE` wins, otherwise a profile named after `year` is used if it exists.
+pub fn resolve_profile(year: Option<u16>) -> Option<String> {
+    if let Ok(profile) = env::var(PROFILE_VAR) {
+        return Some(profile);
+    }
+
+    let year = year?.to_string();
+    session_path(Some(&year))
+        .is_ok_and(|path| path.exists())
+        .then_some(year)
+}
+
+/// names of all profiles with a session file, the default profile excluded.
+pub fn list_profiles() -> Result<Vec<String>, SessionError> {
+    let mut profiles: Vec<String> = fs::read_dir(home_dir()?)
+        .map_err(SessionError::Io)?
+        .filter_map(|entry| entry.ok()?.file_name().into_string().ok())
+        .filter_map(|name| {
+            name.strip_prefix(".adventofcode.")?
+                .strip_suffix(".session")
+                .filter(|profile| is_valid_profile(profile))
+                .map(String::from)
+        })
+        .collect();
+    profiles.sort();
+    Ok(profiles)
+}
+
+pub fn load(profile: Option<&str>) -> Result<Session, SessionError> {
+    if profile.is_none() {
+        if let Ok(token) = env::var(SESSION_VAR) {
+            return Ok(Session {
+                token: token.trim().to_string(),
+                path: None,
+                saved_at: None,
+            });
+        }
+    }
+
+    let path = session_path(profile)?;
+    let token = match fs::read_to_string(&path) {
+        Ok(token) => token.trim().to_string(),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Err(SessionError::NotFound(path)),
+        Err(e) => return Err(SessionError::Io(e)),
+    };
+
+    if token.is_empty() {
+        return Err(SessionError::Empty);
+    }
+
+    let saved_at = fs::metadata(&path).and_then(|m| m.modified()).ok();
+    Ok(Session {
+        token,
+        path: Some(path),
+        saved_at,
+    })
+}
+
+/// writes `contents` to `path` so that only the current user can read it.
+/// an existing file is restricted before anything is written to it.
+pub(crate) fn write_private(path: &Path, contents: &[u8]) -> io::Result<()> {
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+        options.mode(0o600);
+        match fs::set_permissions(path, fs::Permissions::from_mode(0o600)) {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => return Err(e),
+            _ => {}
+        }
+    }
+
+    options.open(path)?.write_all(contents)
+}
+
+pub fn save(profile: Option<&str>, token: &str) -> Result<PathBuf, SessionError> {
+    let token = token.trim();
+    if token.is_empty() {
+        return Err(SessionError::Empty);
+    }
+
+    let path = session_path(profile)?;
+    write_private(&path, token.as_bytes()).map_err(SessionError::Io)?;
+    Ok(path)
+}
+
+/// runs curl with `args` and the session cookie.
+/// the cookie is passed as a header on stdin, so it does not show up in the process list.
+pub fn curl(session: &Session, args: &[&str]) -> io::Result<Output> {
+    let mut child = Command::new("curl")
+        .args(["--header", "@-"])
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+
+    let mut stdin = child.stdin.take().expect("stdin is piped");
+    writeln!(stdin, "Cookie: session={}", session.token)?;
+    drop(stdin);
+
+    child.wait_with_output()
+}
+
+/// asks adventofcode.com whether the token belongs to a logged-in user.
+pub fn validate(session: &Session) -> Result<bool, SessionError> {
+    let null_device = if cfg!(windows) { "NUL" } else { "/dev/null" };
+
+    let output = curl(
+        session,
+        &[
+            "--silent",
+            "--show-error",
+            "--output",
+            null_device,
+            "--write-out",
+            "%{http_code} %{redirect_url}",
+            "https://adventofcode.com/settings",
+        ],
+    )
+    .map_err(SessionError::CurlNotCallable)?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(SessionError::Network(stderr.trim().to_string()));
+    }
+    interpret_response(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// the settings page redirects to the front page if the session is not valid.
+fn interpret_response(response: &str) -> Result<bool, SessionError> {
+    let (status, redirect) = response.trim().split_once(' ').unwrap_or((response, ""));
+    match status.trim() {
+        "200" => Ok(true),
+        status
+            if status.starts_with('3')
+                && redirect.trim_end_matches('/') == "https://adventofcode.com" =>
+        {
+            Ok(false)
+        }
+        "000" => Err(SessionError::Network(
+            "no response from adventofcode.com.".into(),
+        )),
+        status => Err(SessionError::Network(format!(
+            "adventofcode.com answered with status {status}."
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redact() {
+        assert_eq!(redact("0123456789abcdef"), "0123********cdef");
+        assert_eq!(redact("short"), "*****");
+    }
+
+    #[test]
+    fn test_file_name() {
+        assert_eq!(file_name(None).unwrap(), ".adventofcode.session");
+        assert_eq!(
+            file_name(Some("2021")).unwrap(),
+            ".adventofcode.2021.session"
+        );
+        assert_eq!(
+            file_name(Some("work_2-b")).unwrap(),
+            ".adventofcode.work_2-b.session"
+        );
+        for profile in ["", "../x", "a/b", "a.b", "a b"] {
+            assert!(matches!(
+                file_name(Some(profile)),
+                Err(SessionError::InvalidProfile(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_interpret_response() {
+        assert!(interpret_response("200 ").unwrap());
+        assert!(!interpret_response("302 https://adventofcode.com/").unwrap());
+        assert!(matches!(
+            interpret_response("000 "),
+            Err(SessionError::Network(_))
+        ));
+        assert!(matches!(
+            interpret_response("302 https://adventofcode.com/auth/login"),
+            Err(SessionError::Network(_))
+        ));
+        assert!(matches!(
+            interpret_response("503 "),
+            Err(SessionError::Network(_))
+        ));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_write_private() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let path = env::temp_dir().join(format!("aoc-private-{}", std::process::id()));
+        fs::write(&path, "public").unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o644)).unwrap();
+
+        write_private(&path, b"secret").unwrap();
+        let mode = fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "secret");
+        fs::remove_file(&path).unwrap();
+        assert_eq!(mode & 0o777, 0o600);
+
+        write_private(&path, b"new").unwrap();
+        let mode = fs::metadata(&path).unwrap().permissions().mode();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+}
@@ -0,0 +1,100 @@
+use std::{collections::HashMap, error::Error, fmt::Display};
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum TemplateError {
+    UnknownPlaceholder(String),
+    Unclosed(usize),
+}
+
+impl Display for TemplateError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TemplateError::UnknownPlaceholder(name) => {
+                write!(f, "template uses unknown placeholder \"{{{{{name}}}}}\".")
+            }
+            TemplateError::Unclosed(line) => {
+                write!(f, "template has an unclosed placeholder on line {line}.")
+            }
+        }
+    }
+}
+
+impl Error for TemplateError {}
+
+/// replaces every `{{name}}` in `template` with its value from `vars`.
+/// whitespace inside the braces is ignored, so `{{ name }}` works too.
+/// `{{{{` is rendered as a literal `{{`. closing braces need no escape: a placeholder ends at
+/// the first `}}` after its `{{`, any other `}}` is copied as it is.
+pub fn render(template: &str, vars: &HashMap<&str, String>) -> Result<String, TemplateError> {
+    let mut rendered = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find("{{") {
+        rendered.push_str(&rest[..start]);
+
+        if let Some(after_escape) = rest[start..].strip_prefix("{{{{") {
+            rendered.push_str("{{");
+            rest = after_escape;
+            continue;
+        }
+
+        let after_open = &rest[start + 2..];
+        let Some(end) = after_open.find("}}") else {
+            let offset = template.len() - rest.len() + start;
+            let line = template[..offset].matches('\n').count() + 1;
+            return Err(TemplateError::Unclosed(line));
+        };
+
+        let name = after_open[..end].trim();
+        let value = vars
+            .get(name)
+            .ok_or_else(|| TemplateError::UnknownPlaceholder(name.to_string()))?;
+        rendered.push_str(value);
+
+        rest = &after_open[end + 2..];
+    }
+
+    rendered.push_str(rest);
+    Ok(rendered)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let vars = HashMap::from([("day", "7".to_string()), ("day_padded", "07".to_string())]);
+
+        assert_eq!(
+            render(
+                "DAY {{day}}: read_file(\"inputs\", {{ day }}) // {{day_padded}}",
+                &vars
+            ),
+            Ok("DAY 7: read_file(\"inputs\", 7) // 07".to_string())
+        );
+        assert_eq!(
+            render("{{title}}", &vars),
+            Err(TemplateError::UnknownPlaceholder("title".to_string()))
+        );
+        assert_eq!(render("a\n{{day", &vars), Err(TemplateError::Unclosed(2)));
+    }
+
+    #[test]
+    fn test_render_escaped() {
+        let vars = HashMap::from([("day", "7".to_string())]);
+
+        assert_eq!(
+            render("format!(\"{{{{{{day}}}}\")", &vars),
+            Ok("format!(\"{{7}}\")".to_string())
+        );
+        assert_eq!(
+            render("{{{{day}} {{{{{{{{", &vars),
+            Ok("{{day}} {{{{".to_string())
+        );
+        assert_eq!(
+            render("{{day}}}} }}{{day}} }}", &vars),
+            Ok("7}} }}7 }}".to_string())
+        );
+    }
+}
@@ -0,0 +1,299 @@
+//! Animates puzzle states in the terminal.
+//!
+//! States implement [`Visualise`] to render themselves as text. Solutions pass them to [`frame`],
+//! or use `Runner::visualise` for a `Simulation`. The animation is only compiled with the
+//! `visualise` feature and only shown if `AOC_VISUALISE` is set, which `cargo solve <day> --visualise` does.
+//! Without the feature, [`frame`] does nothing and states are never rendered.
+
+use crate::helpers::{Grid, Point2};
+use std::fmt::Display;
+
+/// turns on the animation if the `visualise` feature is enabled.
+pub const ENABLE_VAR: &str = "AOC_VISUALISE";
+/// frames per second of the animation, defaults to 10.
+pub const FPS_VAR: &str = "AOC_VISUALISE_FPS";
+
+/// a puzzle state that can be shown as a frame of text.
+pub trait Visualise {
+    fn frame(&self) -> String;
+}
+
+impl<T: Display> Visualise for Grid<T> {
+    fn frame(&self) -> String {
+        self.to_string()
+    }
+}
+
+/// the bounding box of `points` with each point drawn as its character. later points are drawn over earlier ones.
+pub fn points(points: impl IntoIterator<Item = (Point2, char)>) -> String {
+    let points: Vec<_> = points.into_iter().collect();
+    let Some(min_x) = points.iter().map(|(p, _)| p.x).min() else {
+        return String::new();
+    };
+    let min_y = points.iter().map(|(p, _)| p.y).min().unwrap();
+    let max_x = points.iter().map(|(p, _)| p.x).max().unwrap();
+    let max_y = points.iter().map(|(p, _)| p.y).max().unwrap();
+
+    let mut canvas = Grid::new(
+        (max_x - min_x + 1) as usize,
+        (max_y - min_y + 1) as usize,
+        '.',
+    );
+    for (point, c) in points {
+        canvas[((point.y - min_y) as usize, (point.x - min_x) as usize)] = c;
+    }
+    canvas.to_string()
+}
+
+/// stacks drawn side by side from the bottom up, with their numbers below, like the crates of day 5.
+pub fn stacks(stacks: &[Vec<char>]) -> String {
+    let height = stacks.iter().map(Vec::len).max().unwrap_or_default();
+    let mut lines: Vec<String> = (0..height)
+        .rev()
+        .map(|level| {
+            let row: Vec<String> = stacks
+                .iter()
+                .map(|stack| match stack.get(level) {
+                    Some(item) => format!("[{item}]"),
+                    None => "   ".into(),
+                })
+                .collect();
+            row.join(" ").trim_end().to_string()
+        })
+        .collect();
+    let labels: Vec<String> = (1..=stacks.len()).map(|n| format!("{n:^3}")).collect();
+    lines.push(labels.join(" ").trim_end().to_string());
+    lines.join("\n")
+}
+
+/// shows `state` as the next frame of the animation and waits for the frame's turn.
+#[cfg(feature = "visualise")]
+pub fn frame<T: Visualise + ?Sized>(state: &T) {
+    terminal::frame(state);
+}
+
+/// does nothing, the `visualise` feature is disabled.
+#[cfg(not(feature = "visualise"))]
+#[inline(always)]
+pub fn frame<T: Visualise + ?Sized>(_state: &T) {}
+
+/// whether frames are shown. `false` without the `visualise` feature.
+pub fn is_active() -> bool {
+    cfg!(feature = "visualise") && std::env::var_os(ENABLE_VAR).is_some_and(|v| !v.is_empty())
+}
+
+/// ends the animation after a key press and restores the terminal. called by `solve!` after each part.
+pub fn finish() {
+    #[cfg(feature = "visualise")]
+    terminal::finish();
+}
+
+#[cfg(feature = "visualise")]
+mod terminal {
+    use super::{is_active, Visualise, FPS_VAR};
+    use crossterm::{
+        cursor,
+        event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+        execute, queue,
+        style::Print,
+        terminal::{self, ClearType},
+    };
+    use std::{
+        io::{self, IsTerminal, Stdout, Write},
+        process,
+        sync::{Mutex, Once},
+        time::{Duration, Instant},
+    };
+
+    const DEFAULT_FPS: u32 = 10;
+    const MAX_FPS: u32 = 1000;
+
+    enum State {
+        NotStarted,
+        Running(Animation),
+        /// quit by the user or not possible, frames are skipped.
+        Closed,
+    }
+
+    static STATE: Mutex<State> = Mutex::new(State::NotStarted);
+
+    struct Animation {
+        out: Stdout,
+        fps: u32,
+        paused: bool,
+        frames: usize,
+        last_frame: String,
+    }
+
+    impl Animation {
+        fn start() -> io::Result<Self> {
+            let fps = std::env::var(FPS_VAR)
+                .ok()
+                .and_then(|fps| fps.parse().ok())
+                .filter(|&fps| fps > 0)
+                .unwrap_or(DEFAULT_FPS)
+                .min(MAX_FPS);
+
+            restore_on_panic();
+            let mut out = io::stdout();
+            terminal::enable_raw_mode()?;
+            execute!(out, terminal::EnterAlternateScreen, cursor::Hide)?;
+            Ok(Animation {
+                out,
+                fps,
+                paused: false,
+                frames: 0,
+                last_frame: String::new(),
+            })
+        }
+
+        fn draw(&mut self, frame: &str, status: &str) -> io::Result<()> {
+            queue!(self.out, terminal::Clear(ClearType::All))?;
+            let mut row = 0;
+            for line in frame.lines() {
+                queue!(self.out, cursor::MoveTo(0, row), Print(line))?;
+                row += 1;
+            }
+            queue!(self.out, cursor::MoveTo(0, row + 1), Print(status))?;
+            self.out.flush()
+        }
+
+        fn status(&self) -> String {
+            let state = if self.paused {
+                "paused, → to step".to_string()
+            } else {
+                format!("{} fps, +/- to change", self.fps)
+            };
+            format!(
+                "frame {} · {state} · space to pause · q to skip the animation",
+                self.frames
+            )
+        }
+
+        /// shows a frame and waits until the next one is due. returns `false` if the user quit.
+        fn show(&mut self, frame: String) -> io::Result<bool> {
+            self.frames += 1;
+            self.last_frame = frame;
+            let frame = self.last_frame.clone();
+            self.draw(&frame, &self.status())?;
+
+            let mut due = Instant::now() + Duration::from_secs(1) / self.fps;
+            loop {
+                let timeout = due.saturating_duration_since(Instant::now());
+                let has_event = self.paused || event::poll(timeout)?;
+                if !has_event {
+                    return Ok(true);
+                }
+
+                match read_key()? {
+                    Some(KeyCode::Char(' ')) => {
+                        self.paused = !self.paused;
+                        due = Instant::now();
+                    }
+                    Some(KeyCode::Right | KeyCode::Char('n')) if self.paused => return Ok(true),
+                    Some(KeyCode::Char('+')) => self.fps = (self.fps * 2).min(MAX_FPS),
+                    Some(KeyCode::Char('-')) => self.fps = (self.fps / 2).max(1),
+                    Some(KeyCode::Char('q') | KeyCode::Esc) => return Ok(false),
+                    _ => continue,
+                }
+                self.draw(&frame, &self.status())?;
+            }
+        }
+    }
+
+    impl Drop for Animation {
+        fn drop(&mut self) {
+            restore();
+        }
+    }
+
+    /// the next key press. raw mode swallows ctrl-c, so it exits here.
+    fn read_key() -> io::Result<Option<KeyCode>> {
+        match event::read()? {
+            Event::Key(KeyEvent {
+                code: KeyCode::Char('c'),
+                modifiers: KeyModifiers::CONTROL,
+                ..
+            }) => {
+                restore();
+                process::exit(130);
+            }
+            Event::Key(KeyEvent {
+                code,
+                kind: KeyEventKind::Press,
+                ..
+            }) => Ok(Some(code)),
+            _ => Ok(None),
+        }
+    }
+
+    fn restore() {
+        let _ = execute!(io::stdout(), terminal::LeaveAlternateScreen, cursor::Show);
+        let _ = terminal::disable_raw_mode();
+    }
+
+    /// a panic would leave the terminal in raw mode without a visible message.
+    fn restore_on_panic() {
+        static HOOK: Once = Once::new();
+        HOOK.call_once(|| {
+            let hook = std::panic::take_hook();
+            std::panic::set_hook(Box::new(move |info| {
+                restore();
+                hook(info);
+            }));
+        });
+    }
+
+    pub fn frame<T: Visualise + ?Sized>(state: &T) {
+        let mut current = STATE.lock().unwrap_or_else(|e| e.into_inner());
+        if let State::NotStarted = *current {
+            *current = if is_active() && io::stdout().is_terminal() {
+                Animation::start().map_or(State::Closed, State::Running)
+            } else {
+                State::Closed
+            };
+        }
+
+        if let State::Running(animation) = &mut *current {
+            if !animation.show(state.frame()).unwrap_or(false) {
+                *current = State::Closed;
+            }
+        }
+    }
+
+    pub fn finish() {
+        let mut current = STATE.lock().unwrap_or_else(|e| e.into_inner());
+        if let State::Running(animation) = &mut *current {
+            let status = format!(
+                "frame {} · done, press any key to continue",
+                animation.frames
+            );
+            let frame = std::mem::take(&mut animation.last_frame);
+            let _ = animation.draw(&frame, &status);
+            while !matches!(read_key(), Ok(Some(_)) | Err(_)) {}
+        }
+        *current = State::NotStarted;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_points() {
+        let frame = points([
+            (Point2::new(-1, 0), '#'),
+            (Point2::new(1, 1), '#'),
+            (Point2::new(1, 1), 'H'),
+        ]);
+        assert_eq!(frame, "#..\n..H");
+        assert_eq!(points([]), "");
+    }
+
+    #[test]
+    fn test_stacks() {
+        let frame = stacks(&[vec!['Z', 'N'], vec!['M', 'C', 'D'], vec!['P']]);
+        assert_eq!(frame, "    [D]\n[N] [C]\n[Z] [M] [P]\n 1   2   3");
+    }
+}